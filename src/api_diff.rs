@@ -0,0 +1,681 @@
+use crate::source_code::SourceCode;
+use crate::types::{Namespace, Symbol, SymbolKind};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A difference in a single symbol between two versions of a public API
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum SymbolChange {
+    Added {
+        namespace: String,
+        symbol: Symbol,
+    },
+    Removed {
+        namespace: String,
+        symbol: Symbol,
+    },
+    Changed {
+        namespace: String,
+        old: Symbol,
+        new: Symbol,
+    },
+}
+
+impl SymbolChange {
+    /// Return the name of the namespace containing the symbol.
+    pub fn namespace(&self) -> &str {
+        match self {
+            Self::Added { namespace, .. }
+            | Self::Removed { namespace, .. }
+            | Self::Changed { namespace, .. } => namespace,
+        }
+    }
+
+    /// Return the name of the symbol.
+    pub fn symbol_name(&self) -> &str {
+        match self {
+            Self::Added { symbol, .. } | Self::Removed { symbol, .. } => &symbol.name,
+            Self::Changed { new, .. } => &new.name,
+        }
+    }
+
+    fn sort_key(&self) -> SymbolKey<'_> {
+        let symbol = match self {
+            Self::Added { symbol, .. } | Self::Removed { symbol, .. } => symbol,
+            Self::Changed { new, .. } => new,
+        };
+        (self.namespace(), &symbol.name, symbol.kind)
+    }
+
+    /// Classify the change conservatively: additions are additive, whilst removals and changes
+    /// are breaking.
+    ///
+    /// Since any difference in the source code counts as a change, editing a doc comment alone
+    /// makes a change breaking. To avoid that, compare symbols without their docs with
+    /// [`diff_public_api_with`], or classify such changes with [`SemverReport::with_classifier`].
+    pub fn version_bump(&self) -> VersionBump {
+        match self {
+            Self::Added { .. } => VersionBump::Minor,
+            Self::Removed { .. } | Self::Changed { .. } => VersionBump::Major,
+        }
+    }
+}
+
+/// The differences between two versions of a public API
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApiDiff {
+    /// The changes, sorted by namespace and symbol name
+    pub changes: Vec<SymbolChange>,
+}
+
+impl ApiDiff {
+    /// Check whether the two versions have the same public API.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// The version bump required by a change, following semantic versioning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VersionBump {
+    /// The change doesn't affect the public API
+    Patch,
+    /// The change is additive
+    Minor,
+    /// The change is breaking
+    Major,
+}
+
+/// A change to the public API along with the version bump it requires
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClassifiedChange {
+    pub change: SymbolChange,
+    pub version_bump: VersionBump,
+}
+
+/// The changes between two versions of a public API, classified per semantic versioning
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SemverReport {
+    /// The smallest version bump that covers all the changes
+    pub required_bump: VersionBump,
+    pub changes: Vec<ClassifiedChange>,
+}
+
+impl SemverReport {
+    /// Classify the changes with [`SymbolChange::version_bump`].
+    pub fn new(diff: ApiDiff) -> Self {
+        Self::with_classifier(diff, |_| None)
+    }
+
+    /// Classify the changes, letting a language-specific classifier override the default.
+    ///
+    /// For example, a Rust classifier could deem a variant added to a `#[non_exhaustive]` enum
+    /// to be additive rather than breaking.
+    ///
+    /// # Parameters
+    /// * `diff` - The changes to classify
+    /// * `classifier` - A function returning the version bump for a change, or `None` to use
+    ///   the default classification
+    ///
+    /// # Returns
+    /// The classified changes
+    pub fn with_classifier<F>(diff: ApiDiff, classifier: F) -> Self
+    where
+        F: Fn(&SymbolChange) -> Option<VersionBump>,
+    {
+        let changes: Vec<ClassifiedChange> = diff
+            .changes
+            .into_iter()
+            .map(|change| {
+                let version_bump = classifier(&change).unwrap_or_else(|| change.version_bump());
+                ClassifiedChange {
+                    change,
+                    version_bump,
+                }
+            })
+            .collect();
+        let required_bump = changes
+            .iter()
+            .map(|change| change.version_bump)
+            .max()
+            .unwrap_or(VersionBump::Patch);
+        Self {
+            required_bump,
+            changes,
+        }
+    }
+
+    /// Check whether any change is breaking.
+    pub fn is_breaking(&self) -> bool {
+        self.required_bump == VersionBump::Major
+    }
+}
+
+/// Compare two versions of a public API.
+///
+/// Symbols are matched by namespace, name and kind, so that a trait and a macro sharing a name
+/// are compared separately. Symbols sharing all three (e.g. alternative definitions for different
+/// platforms) are matched by condition, and otherwise in the order they appear. A symbol is
+/// considered changed if its source code, condition or variants differ, including their doc
+/// comments, regardless of the order its variants are in. It's also considered changed if the
+/// features it requires or whether it's sealed differ, even if its source code doesn't.
+///
+/// # Parameters
+/// * `old` - The namespaces of the old version
+/// * `new` - The namespaces of the new version
+///
+/// # Returns
+/// The added, removed and changed symbols
+pub fn diff_public_api(old: &[Namespace], new: &[Namespace]) -> ApiDiff {
+    diff_public_api_with(old, new, |old_symbol, new_symbol| {
+        get_definitions(old_symbol) == get_definitions(new_symbol)
+            && get_required_features(old_symbol) == get_required_features(new_symbol)
+            && old_symbol.is_sealed == new_symbol.is_sealed
+    })
+}
+
+/// Return the features required by the symbol, regardless of the order they were declared in.
+fn get_required_features(symbol: &Symbol) -> BTreeSet<&str> {
+    symbol
+        .required_features
+        .iter()
+        .map(String::as_str)
+        .collect()
+}
+
+/// Return the condition and source code of each of the symbol's definitions.
+fn get_definitions(symbol: &Symbol) -> Vec<(Option<&str>, &SourceCode)> {
+    let variant_definitions = symbol
+        .variants
+        .iter()
+        .map(|variant| (Some(variant.condition.as_str()), &variant.source_code));
+    let mut definitions: Vec<(Option<&str>, &SourceCode)> =
+        std::iter::once((symbol.condition.as_deref(), &symbol.source_code))
+            .chain(variant_definitions)
+            .collect();
+    definitions.sort_by_key(|(condition, _)| *condition);
+    definitions
+}
+
+/// Pair the old and new versions of the symbols sharing a key.
+///
+/// Versions with the same condition are paired first, so that removing one of several
+/// conditional definitions isn't reported as the others changing. The rest are paired in order,
+/// and any left over were removed or added.
+fn pair_versions<'a>(
+    old_versions: &[&'a Symbol],
+    new_versions: &[&'a Symbol],
+) -> Vec<(Option<&'a Symbol>, Option<&'a Symbol>)> {
+    let mut unpaired_new_versions: Vec<Option<&Symbol>> =
+        new_versions.iter().copied().map(Some).collect();
+    let mut pairs = Vec::with_capacity(old_versions.len().max(new_versions.len()));
+    let mut unpaired_old_versions = Vec::new();
+    for old_symbol in old_versions {
+        let new_symbol = unpaired_new_versions.iter_mut().find(|new_symbol| {
+            new_symbol.is_some_and(|new_symbol| new_symbol.condition == old_symbol.condition)
+        });
+        match new_symbol {
+            Some(new_symbol) => pairs.push((Some(*old_symbol), new_symbol.take())),
+            None => unpaired_old_versions.push(*old_symbol),
+        }
+    }
+    let mut unpaired_new_versions = unpaired_new_versions.into_iter().flatten();
+    for old_symbol in unpaired_old_versions {
+        pairs.push((Some(old_symbol), unpaired_new_versions.next()));
+    }
+    pairs.extend(unpaired_new_versions.map(|new_symbol| (None, Some(new_symbol))));
+    pairs
+}
+
+/// Compare two versions of a public API, deciding with `is_equivalent` whether a symbol changed.
+///
+/// Symbols are matched as in [`diff_public_api`]. This allows extractors to ignore differences
+/// that don't affect the API, such as edits to doc comments or formatting.
+///
+/// # Parameters
+/// * `old` - The namespaces of the old version
+/// * `new` - The namespaces of the new version
+/// * `is_equivalent` - A function returning whether the old and new versions of a symbol are
+///   the same as far as the API is concerned
+///
+/// # Returns
+/// The added, removed and changed symbols
+pub fn diff_public_api_with<F>(old: &[Namespace], new: &[Namespace], is_equivalent: F) -> ApiDiff
+where
+    F: Fn(&Symbol, &Symbol) -> bool,
+{
+    let old_symbols = index_symbols(old);
+    let new_symbols = index_symbols(new);
+
+    let keys: BTreeSet<&SymbolKey> = old_symbols.keys().chain(new_symbols.keys()).collect();
+    let mut changes: Vec<SymbolChange> = Vec::new();
+    for key in keys {
+        let old_versions = old_symbols.get(key).map_or(&[][..], Vec::as_slice);
+        let new_versions = new_symbols.get(key).map_or(&[][..], Vec::as_slice);
+        for versions in pair_versions(old_versions, new_versions) {
+            let namespace = key.0.to_string();
+            match versions {
+                (Some(old_symbol), None) => changes.push(SymbolChange::Removed {
+                    namespace,
+                    symbol: old_symbol.clone(),
+                }),
+                (None, Some(new_symbol)) => changes.push(SymbolChange::Added {
+                    namespace,
+                    symbol: new_symbol.clone(),
+                }),
+                (Some(old_symbol), Some(new_symbol)) if !is_equivalent(old_symbol, new_symbol) => {
+                    changes.push(SymbolChange::Changed {
+                        namespace,
+                        old: old_symbol.clone(),
+                        new: new_symbol.clone(),
+                    })
+                }
+                _ => {}
+            }
+        }
+    }
+
+    changes.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+    ApiDiff { changes }
+}
+
+type SymbolKey<'a> = (&'a str, &'a str, SymbolKind);
+
+fn index_symbols(namespaces: &[Namespace]) -> BTreeMap<SymbolKey<'_>, Vec<&Symbol>> {
+    let mut symbols: BTreeMap<SymbolKey, Vec<&Symbol>> = BTreeMap::new();
+    for namespace in namespaces {
+        for symbol in &namespace.symbols {
+            symbols
+                .entry((namespace.name.as_str(), symbol.name.as_str(), symbol.kind))
+                .or_default()
+                .push(symbol);
+        }
+    }
+    symbols
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::fixtures::{make_namespace, make_symbol};
+    use crate::types::SymbolVariant;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    #[test]
+    fn identical_apis() {
+        let namespaces = vec![make_namespace(
+            "crate",
+            vec![make_symbol("foo", "pub fn foo() {}")],
+        )];
+
+        let diff = diff_public_api(&namespaces, &namespaces);
+
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn added_removed_and_changed_symbols() {
+        let old = vec![make_namespace(
+            "crate",
+            vec![
+                make_symbol("foo", "pub fn foo() {}"),
+                make_symbol("bar", "pub fn bar() {}"),
+            ],
+        )];
+        let new = vec![
+            make_namespace("crate", vec![make_symbol("foo", "pub fn foo(x: u8) {}")]),
+            make_namespace("crate::inner", vec![make_symbol("baz", "pub fn baz() {}")]),
+        ];
+
+        let diff = diff_public_api(&old, &new);
+
+        assert_eq!(
+            diff.changes,
+            vec![
+                SymbolChange::Removed {
+                    namespace: "crate".to_string(),
+                    symbol: make_symbol("bar", "pub fn bar() {}"),
+                },
+                SymbolChange::Changed {
+                    namespace: "crate".to_string(),
+                    old: make_symbol("foo", "pub fn foo() {}"),
+                    new: make_symbol("foo", "pub fn foo(x: u8) {}"),
+                },
+                SymbolChange::Added {
+                    namespace: "crate::inner".to_string(),
+                    symbol: make_symbol("baz", "pub fn baz() {}"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn symbols_of_different_kinds_with_same_name() {
+        let make_trait = |source_code| Symbol {
+            kind: SymbolKind::Trait,
+            ..make_symbol("Serialize", source_code)
+        };
+        let macro_symbol = Symbol {
+            kind: SymbolKind::Macro,
+            ..make_symbol("Serialize", "pub use serde_derive::Serialize;")
+        };
+        let old = vec![make_namespace(
+            "serde",
+            vec![make_trait("pub trait Serialize {}"), macro_symbol.clone()],
+        )];
+        let new = vec![make_namespace(
+            "serde",
+            vec![
+                make_trait("pub trait Serialize { fn serialize(&self); }"),
+                macro_symbol,
+            ],
+        )];
+
+        let diff = diff_public_api(&old, &new);
+
+        assert_eq!(
+            diff.changes,
+            vec![SymbolChange::Changed {
+                namespace: "serde".to_string(),
+                old: make_trait("pub trait Serialize {}"),
+                new: make_trait("pub trait Serialize { fn serialize(&self); }"),
+            }]
+        );
+    }
+
+    #[test]
+    fn symbols_of_same_kind_with_same_name() {
+        let old = vec![make_namespace(
+            "crate",
+            vec![make_symbol("connect", "#[cfg(unix)]\npub fn connect() {}")],
+        )];
+        let new = vec![make_namespace(
+            "crate",
+            vec![
+                make_symbol("connect", "#[cfg(unix)]\npub fn connect() {}"),
+                make_symbol("connect", "#[cfg(windows)]\npub fn connect() {}"),
+            ],
+        )];
+
+        let diff = diff_public_api(&old, &new);
+
+        assert_eq!(
+            diff.changes,
+            vec![SymbolChange::Added {
+                namespace: "crate".to_string(),
+                symbol: make_symbol("connect", "#[cfg(windows)]\npub fn connect() {}"),
+            }]
+        );
+    }
+
+    #[test]
+    fn changed_conditional_variant() {
+        let make_connect = |windows_source_code: &str| Symbol {
+            condition: Some("unix".to_string()),
+            variants: vec![SymbolVariant {
+                condition: "windows".to_string(),
+                source_code: windows_source_code.into(),
+                file: Arc::from(Path::new("src/lib.rs")),
+                start_line: 2,
+                end_line: 2,
+            }],
+            ..make_symbol("connect", "pub fn connect() {}")
+        };
+        let old = vec![make_namespace(
+            "crate",
+            vec![make_connect("pub fn connect() {}")],
+        )];
+        let new = vec![make_namespace(
+            "crate",
+            vec![make_connect("pub fn connect(timeout: u64) {}")],
+        )];
+
+        let diff = diff_public_api(&old, &new);
+
+        assert_eq!(diff.changes.len(), 1);
+        assert!(matches!(diff.changes[0], SymbolChange::Changed { .. }));
+    }
+
+    #[test]
+    fn reordered_conditional_variants() {
+        let make_variant = |condition: &str, source_code: &str| SymbolVariant {
+            condition: condition.to_string(),
+            source_code: source_code.into(),
+            file: Arc::from(Path::new("src/lib.rs")),
+            start_line: 2,
+            end_line: 2,
+        };
+        let unix = make_variant("unix", "pub fn connect() {}");
+        let windows = make_variant("windows", "pub fn connect(port: u16) {}");
+        let make_connect = |primary: &SymbolVariant, variant: &SymbolVariant| Symbol {
+            condition: Some(primary.condition.clone()),
+            variants: vec![variant.clone()],
+            ..make_symbol("connect", &primary.source_code)
+        };
+        let old = vec![make_namespace("crate", vec![make_connect(&unix, &windows)])];
+        let new = vec![make_namespace("crate", vec![make_connect(&windows, &unix)])];
+
+        let diff = diff_public_api(&old, &new);
+
+        assert!(diff.changes.is_empty());
+    }
+
+    #[test]
+    fn removed_conditional_symbol_is_matched_by_condition() {
+        let make_connect = |condition: &str, source_code: &str| Symbol {
+            condition: Some(condition.to_string()),
+            ..make_symbol("connect", source_code)
+        };
+        let old = vec![make_namespace(
+            "crate",
+            vec![
+                make_connect("unix", "pub fn connect() {}"),
+                make_connect("windows", "pub fn connect(port: u16) {}"),
+            ],
+        )];
+        let new = vec![make_namespace(
+            "crate",
+            vec![make_connect("windows", "pub fn connect(port: u16) {}")],
+        )];
+
+        let diff = diff_public_api(&old, &new);
+
+        assert_eq!(
+            diff.changes,
+            vec![SymbolChange::Removed {
+                namespace: "crate".to_string(),
+                symbol: make_connect("unix", "pub fn connect() {}"),
+            }]
+        );
+    }
+
+    #[test]
+    fn changed_required_features() {
+        let make_client = |required_features: &[&str]| Symbol {
+            required_features: required_features.iter().map(|f| f.to_string()).collect(),
+            ..make_symbol("Client", "pub struct Client;")
+        };
+        let old = vec![make_namespace(
+            "crate",
+            vec![make_client(&["http2", "tls"])],
+        )];
+        let reordered = vec![make_namespace(
+            "crate",
+            vec![make_client(&["tls", "http2"])],
+        )];
+        let new = vec![make_namespace("crate", vec![make_client(&["tls"])])];
+
+        assert!(diff_public_api(&old, &reordered).changes.is_empty());
+        let diff = diff_public_api(&old, &new);
+
+        assert_eq!(diff.changes.len(), 1);
+        assert!(matches!(diff.changes[0], SymbolChange::Changed { .. }));
+    }
+
+    #[test]
+    fn changed_sealing() {
+        let make_handler = |is_sealed| Symbol {
+            is_sealed,
+            ..make_symbol("Handler", "pub trait Handler: private::Sealed {}")
+        };
+        let old = vec![make_namespace("crate", vec![make_handler(true)])];
+        let new = vec![make_namespace("crate", vec![make_handler(false)])];
+
+        let diff = diff_public_api(&old, &new);
+
+        assert_eq!(diff.changes.len(), 1);
+        assert!(matches!(diff.changes[0], SymbolChange::Changed { .. }));
+    }
+
+    #[test]
+    fn moved_symbol_is_removed_and_added() {
+        let old = vec![make_namespace(
+            "crate",
+            vec![make_symbol("foo", "pub fn foo() {}")],
+        )];
+        let new = vec![make_namespace(
+            "crate::inner",
+            vec![make_symbol("foo", "pub fn foo() {}")],
+        )];
+
+        let diff = diff_public_api(&old, &new);
+
+        assert_eq!(diff.changes.len(), 2);
+        assert!(matches!(diff.changes[0], SymbolChange::Removed { .. }));
+        assert!(matches!(diff.changes[1], SymbolChange::Added { .. }));
+    }
+
+    #[test]
+    fn semver_report_without_changes() {
+        let report = SemverReport::new(ApiDiff::default());
+
+        assert_eq!(report.required_bump, VersionBump::Patch);
+        assert!(!report.is_breaking());
+    }
+
+    #[test]
+    fn semver_report_additions_only() {
+        let old = vec![make_namespace("crate", vec![])];
+        let new = vec![make_namespace(
+            "crate",
+            vec![make_symbol("foo", "pub fn foo() {}")],
+        )];
+
+        let report = SemverReport::new(diff_public_api(&old, &new));
+
+        assert_eq!(report.required_bump, VersionBump::Minor);
+        assert_eq!(report.changes[0].version_bump, VersionBump::Minor);
+    }
+
+    #[test]
+    fn semver_report_breaking_change() {
+        let old = vec![make_namespace(
+            "crate",
+            vec![make_symbol("foo", "pub fn foo() {}")],
+        )];
+        let new = vec![make_namespace(
+            "crate",
+            vec![
+                make_symbol("foo", "pub fn foo(x: u8) {}"),
+                make_symbol("bar", "pub fn bar() {}"),
+            ],
+        )];
+
+        let report = SemverReport::new(diff_public_api(&old, &new));
+
+        assert_eq!(report.required_bump, VersionBump::Major);
+        assert!(report.is_breaking());
+    }
+
+    #[test]
+    fn semver_report_custom_classifier() {
+        let old = vec![make_namespace(
+            "crate",
+            vec![make_symbol(
+                "Kind",
+                "#[non_exhaustive]\npub enum Kind { A }",
+            )],
+        )];
+        let new = vec![make_namespace(
+            "crate",
+            vec![make_symbol(
+                "Kind",
+                "#[non_exhaustive]\npub enum Kind { A, B }",
+            )],
+        )];
+        let classifier = |change: &SymbolChange| match change {
+            SymbolChange::Changed { old, .. } if old.source_code.contains("#[non_exhaustive]") => {
+                Some(VersionBump::Minor)
+            }
+            _ => None,
+        };
+
+        let report = SemverReport::with_classifier(diff_public_api(&old, &new), classifier);
+
+        assert_eq!(report.required_bump, VersionBump::Minor);
+    }
+
+    fn make_documented_symbols() -> (Vec<Namespace>, Vec<Namespace>) {
+        let old = vec![make_namespace(
+            "crate",
+            vec![make_symbol("foo", "/// Old docs\npub fn foo() {}")],
+        )];
+        let new = vec![make_namespace(
+            "crate",
+            vec![make_symbol("foo", "/// New docs\npub fn foo() {}")],
+        )];
+        (old, new)
+    }
+
+    fn strip_doc_lines(symbol: &Symbol) -> Vec<&str> {
+        symbol
+            .source_code
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("///"))
+            .collect()
+    }
+
+    #[test]
+    fn docs_only_change_is_breaking_by_default() {
+        let (old, new) = make_documented_symbols();
+
+        let report = SemverReport::new(diff_public_api(&old, &new));
+
+        assert!(report.is_breaking());
+    }
+
+    #[test]
+    fn docs_only_change_ignored_by_custom_comparison() {
+        let (old, new) = make_documented_symbols();
+
+        let diff = diff_public_api_with(&old, &new, |old_symbol, new_symbol| {
+            strip_doc_lines(old_symbol) == strip_doc_lines(new_symbol)
+        });
+
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn docs_only_change_classified_as_patch() {
+        let (old, new) = make_documented_symbols();
+        let classifier = |change: &SymbolChange| match change {
+            SymbolChange::Changed { old, new, .. }
+                if strip_doc_lines(old) == strip_doc_lines(new) =>
+            {
+                Some(VersionBump::Patch)
+            }
+            _ => None,
+        };
+
+        let report = SemverReport::with_classifier(diff_public_api(&old, &new), classifier);
+
+        assert_eq!(report.required_bump, VersionBump::Patch);
+        assert_eq!(report.changes.len(), 1);
+    }
+}
@@ -0,0 +1,73 @@
+use crate::ExtractionError;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A flag to abort a long extraction cleanly from another thread.
+///
+/// Clones share the same flag, so the caller keeps one clone and passes another to the
+/// extractor via `ExtractorOptions`. Extractors should call [`CancellationToken::check`]
+/// between units of work (e.g. files or namespaces).
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    is_cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request the cancellation of the work using this token.
+    pub fn cancel(&self) {
+        self.is_cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Check whether cancellation has been requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.is_cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Return an error if cancellation has been requested.
+    ///
+    /// # Returns
+    /// Nothing, or `ExtractionError::Cancelled` if cancellation has been requested
+    pub fn check(&self) -> Result<(), ExtractionError> {
+        if self.is_cancelled() {
+            Err(ExtractionError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl PartialEq for CancellationToken {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.is_cancelled, &other.is_cancelled)
+    }
+}
+
+impl Eq for CancellationToken {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_token_is_not_cancelled() {
+        let token = CancellationToken::new();
+
+        assert!(!token.is_cancelled());
+        assert!(token.check().is_ok());
+    }
+
+    #[test]
+    fn cancellation_is_shared_by_clones() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+
+        token.cancel();
+
+        assert!(clone.is_cancelled());
+        assert!(matches!(clone.check(), Err(ExtractionError::Cancelled)));
+    }
+}
@@ -0,0 +1,176 @@
+/// Convert a raw doc comment into plain Markdown.
+///
+/// Line comment markers (e.g. `///`, `//!`) and block comment delimiters (e.g. `/** */`, `/*! */`)
+/// are removed, as are the leading asterisks of block comment lines. The indentation common to all
+/// lines is then stripped, so that indented code blocks and lists are preserved.
+///
+/// # Parameters
+/// * `raw` - The doc comment as it appears in the source code
+///
+/// # Returns
+/// The Markdown content of the doc comment
+pub fn normalise_doc_comment(raw: &str) -> String {
+    let raw = raw.trim();
+    let lines = match strip_block_delimiters(raw) {
+        Some(content) => strip_block_asterisks(content),
+        None => raw.lines().map(strip_line_marker).collect(),
+    };
+    dedent(&lines)
+}
+
+fn strip_block_delimiters(comment: &str) -> Option<&str> {
+    let content = comment
+        .strip_prefix("/**")
+        .or_else(|| comment.strip_prefix("/*!"))?;
+    Some(content.strip_suffix("*/").unwrap_or(content))
+}
+
+fn strip_block_asterisks(content: &str) -> Vec<&str> {
+    let lines: Vec<&str> = content.lines().collect();
+    let is_decorated = lines
+        .iter()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .all(|line| line.trim_start().starts_with('*'));
+    if !is_decorated {
+        return lines;
+    }
+
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| {
+            if index == 0 {
+                line
+            } else {
+                let trimmed = line.trim_start();
+                trimmed.strip_prefix('*').unwrap_or(trimmed)
+            }
+        })
+        .collect()
+}
+
+fn strip_line_marker(line: &str) -> &str {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("////") {
+        // Four or more slashes make an ordinary comment, not a doc comment
+        return line;
+    }
+    trimmed
+        .strip_prefix("///")
+        .or_else(|| trimmed.strip_prefix("//!"))
+        .unwrap_or(line)
+}
+
+fn dedent(lines: &[&str]) -> String {
+    let indentation = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+        .min()
+        .unwrap_or(0);
+
+    let dedented: Vec<&str> = lines
+        .iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                ""
+            } else {
+                line[indentation..].trim_end()
+            }
+        })
+        .collect();
+
+    let start = dedented.iter().position(|line| !line.is_empty());
+    let end = dedented.iter().rposition(|line| !line.is_empty());
+    match (start, end) {
+        (Some(start), Some(end)) => dedented[start..=end].join("\n"),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outer_line_comments() {
+        let raw = "/// First line\n/// Second line";
+
+        let normalised = normalise_doc_comment(raw);
+
+        assert_eq!(normalised, "First line\nSecond line");
+    }
+
+    #[test]
+    fn inner_line_comments() {
+        let raw = "//! Crate docs\n//!\n//! More docs";
+
+        let normalised = normalise_doc_comment(raw);
+
+        assert_eq!(normalised, "Crate docs\n\nMore docs");
+    }
+
+    #[test]
+    fn indented_line_comments() {
+        let raw = "    /// Indented\n    /// comment";
+
+        let normalised = normalise_doc_comment(raw);
+
+        assert_eq!(normalised, "Indented\ncomment");
+    }
+
+    #[test]
+    fn nested_indentation_preserved() {
+        let raw = "/// List:\n///   - Item";
+
+        let normalised = normalise_doc_comment(raw);
+
+        assert_eq!(normalised, "List:\n  - Item");
+    }
+
+    #[test]
+    fn ordinary_line_comment_kept() {
+        let raw = "//// not a doc";
+
+        let normalised = normalise_doc_comment(raw);
+
+        assert_eq!(normalised, "//// not a doc");
+    }
+
+    #[test]
+    fn decorated_block_comment() {
+        let raw = "/**\n * First line\n *\n * Second line\n */";
+
+        let normalised = normalise_doc_comment(raw);
+
+        assert_eq!(normalised, "First line\n\nSecond line");
+    }
+
+    #[test]
+    fn undecorated_block_comment() {
+        let raw = "/*!\n    Crate docs\n    More docs\n*/";
+
+        let normalised = normalise_doc_comment(raw);
+
+        assert_eq!(normalised, "Crate docs\nMore docs");
+    }
+
+    #[test]
+    fn single_line_block_comment() {
+        let raw = "/** Short docs */";
+
+        let normalised = normalise_doc_comment(raw);
+
+        assert_eq!(normalised, "Short docs");
+    }
+
+    #[test]
+    fn empty_comment() {
+        let raw = "///";
+
+        let normalised = normalise_doc_comment(raw);
+
+        assert_eq!(normalised, "");
+    }
+}
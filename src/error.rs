@@ -1,19 +1,241 @@
+use crate::fuzzy;
+use std::fmt;
+use std::ops::Range;
+use std::path::PathBuf;
 use thiserror::Error;
 
 /// Error whilst extracting public API
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum ExtractionError {
     #[error(transparent)]
     Io(#[from] std::io::Error),
     #[error("{0}")]
     Malformed(String),
+    #[error("{0}")]
+    Syntax(SourceDiagnostic),
+    #[error("{0} is not supported by this extractor")]
+    Unsupported(String),
+    #[error("Extraction was cancelled")]
+    Cancelled,
+}
+
+impl ExtractionError {
+    /// Attach the path of the offending file to a syntax error.
+    ///
+    /// Other errors are returned unchanged.
+    pub fn with_file(self, file: impl Into<PathBuf>) -> Self {
+        match self {
+            Self::Syntax(diagnostic) => Self::Syntax(SourceDiagnostic {
+                file: Some(file.into()),
+                ..diagnostic
+            }),
+            error => error,
+        }
+    }
+}
+
+/// A problem located in a specific part of a source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceDiagnostic {
+    /// The file containing the problem, if known
+    pub file: Option<PathBuf>,
+    /// The byte range of the offending code
+    pub span: Range<usize>,
+    /// The offending code
+    pub snippet: String,
+    pub message: String,
+}
+
+impl fmt::Display for SourceDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(file) = &self.file {
+            write!(f, " in {}", file.display())?;
+        }
+        write!(
+            f,
+            " at bytes {}..{}: {}",
+            self.span.start, self.span.end, self.snippet
+        )
+    }
+}
+
+/// Serialise the error as its message, along with the structured diagnostic of syntax errors.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ExtractionError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let diagnostic = match self {
+            Self::Syntax(diagnostic) => Some(diagnostic),
+            _ => None,
+        };
+        let mut state = serializer.serialize_struct("ExtractionError", 2)?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("diagnostic", &diagnostic)?;
+        state.end()
+    }
 }
 
 /// Error whilst resolving a dependency path
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum DependencyResolutionError {
     #[error("Failed to retrieve dependency: {0}")]
     RetrievalFailure(String),
-    #[error("'{0}' is not a dependency")]
-    MissingDependency(String),
+    #[error(
+        "'{name}' is not a dependency{}",
+        describe_hints(.suggestions, .searched_paths)
+    )]
+    MissingDependency {
+        name: String,
+        /// Names of dependencies similar to the one requested
+        suggestions: Vec<String>,
+        /// The paths searched for the dependency
+        searched_paths: Vec<PathBuf>,
+    },
+}
+
+impl DependencyResolutionError {
+    /// Report a missing dependency, suggesting similarly named alternatives.
+    ///
+    /// # Parameters
+    /// * `name` - The name of the dependency that could not be found
+    /// * `known_dependencies` - The names of the dependencies that do exist
+    /// * `searched_paths` - The paths searched for the dependency
+    ///
+    /// # Returns
+    /// A `MissingDependency` error
+    pub fn missing<'a, I>(name: &str, known_dependencies: I, searched_paths: Vec<PathBuf>) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        Self::MissingDependency {
+            name: name.to_string(),
+            suggestions: fuzzy::suggest(name, known_dependencies),
+            searched_paths,
+        }
+    }
+}
+
+fn describe_hints(suggestions: &[String], searched_paths: &[PathBuf]) -> String {
+    let mut hints = String::new();
+    if !suggestions.is_empty() {
+        hints.push_str(&format!(". Did you mean: {}?", suggestions.join(", ")));
+    }
+    if !searched_paths.is_empty() {
+        let paths: Vec<String> = searched_paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        let separator = if hints.is_empty() { ". " } else { " " };
+        hints.push_str(&format!("{}Searched: {}", separator, paths.join(", ")));
+    }
+    hints
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for DependencyResolutionError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_diagnostic() -> SourceDiagnostic {
+        SourceDiagnostic {
+            file: None,
+            span: 10..15,
+            snippet: "fn (".to_string(),
+            message: "Failed to parse source file".to_string(),
+        }
+    }
+
+    #[test]
+    fn diagnostic_display_without_file() {
+        let error = ExtractionError::Syntax(make_diagnostic());
+
+        assert_eq!(
+            error.to_string(),
+            "Failed to parse source file at bytes 10..15: fn ("
+        );
+    }
+
+    #[test]
+    fn diagnostic_display_with_file() {
+        let error = ExtractionError::Syntax(make_diagnostic()).with_file("src/lib.rs");
+
+        assert_eq!(
+            error.to_string(),
+            "Failed to parse source file in src/lib.rs at bytes 10..15: fn ("
+        );
+    }
+
+    #[test]
+    fn with_file_ignores_other_errors() {
+        let error = ExtractionError::Malformed("Oops".to_string()).with_file("src/lib.rs");
+
+        assert!(matches!(error, ExtractionError::Malformed(message) if message == "Oops"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialise_syntax_error_with_diagnostic() {
+        let error = ExtractionError::Syntax(make_diagnostic()).with_file("src/lib.rs");
+
+        let json = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "message": "Failed to parse source file in src/lib.rs at bytes 10..15: fn (",
+                "diagnostic": {
+                    "file": "src/lib.rs",
+                    "span": {"start": 10, "end": 15},
+                    "snippet": "fn (",
+                    "message": "Failed to parse source file",
+                },
+            })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialise_other_error_without_diagnostic() {
+        let error = ExtractionError::Cancelled;
+
+        let json = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({"message": "Extraction was cancelled", "diagnostic": null})
+        );
+    }
+
+    #[test]
+    fn missing_dependency_without_context() {
+        let error = DependencyResolutionError::missing("serde", [], vec![]);
+
+        assert_eq!(error.to_string(), "'serde' is not a dependency");
+    }
+
+    #[test]
+    fn missing_dependency_with_suggestions_and_searched_paths() {
+        let error = DependencyResolutionError::missing(
+            "serde_jsn",
+            ["tokio", "serde_json"],
+            vec![PathBuf::from("/project/Cargo.toml")],
+        );
+
+        assert_eq!(
+            error.to_string(),
+            "'serde_jsn' is not a dependency. Did you mean: serde_json? \
+             Searched: /project/Cargo.toml"
+        );
+    }
 }
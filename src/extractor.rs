@@ -1,6 +1,7 @@
 use crate::error::{DependencyResolutionError, ExtractionError};
 use crate::library_metadata::{LibraryMetadata, LibraryMetadataError};
-use crate::types::Namespace;
+use crate::options::ExtractorOptions;
+use crate::types::{ExtractionReport, Symbol};
 use std::path::{Path, PathBuf};
 use tree_sitter::{Language, Parser};
 
@@ -16,11 +17,71 @@ pub trait Extractor<EntryPoint> {
     ) -> Result<LibraryMetadata<EntryPoint>, LibraryMetadataError>;
 
     /// Extract the public API
+    ///
+    /// Problems that only affect individual files or symbols should be reported as warnings,
+    /// so that the rest of the public API is still extracted.
     fn extract_public_api(
         &self,
         metadata: &LibraryMetadata<EntryPoint>,
         parser: &mut Parser,
-    ) -> Result<Vec<Namespace>, ExtractionError>;
+        options: &ExtractorOptions,
+    ) -> Result<ExtractionReport, ExtractionError>;
+
+    /// Extract the public API from an in-memory set of source files instead of the filesystem.
+    ///
+    /// Paths in `files` and in `metadata` must match. Extractors can support this by reading
+    /// files through `InMemorySources`; the default implementation reports it as unsupported.
+    ///
+    /// # Parameters
+    /// * `files` - The paths and contents of the source files
+    /// * `metadata` - The library metadata
+    /// * `parser` - A mutable reference to a configured tree-sitter parser
+    /// * `options` - The extraction options
+    ///
+    /// # Returns
+    /// The extracted public API or an `ExtractionError` if extraction fails
+    fn extract_from_sources(
+        &self,
+        _files: &[(PathBuf, String)],
+        _metadata: &LibraryMetadata<EntryPoint>,
+        _parser: &mut Parser,
+        _options: &ExtractorOptions,
+    ) -> Result<ExtractionReport, ExtractionError> {
+        Err(ExtractionError::Unsupported(
+            "Extraction from in-memory sources".to_string(),
+        ))
+    }
+
+    /// Look up a single symbol in the public API.
+    ///
+    /// The default implementation extracts the whole public API, so extractors that can locate
+    /// a symbol more cheaply should override it.
+    ///
+    /// # Parameters
+    /// * `metadata` - The library metadata
+    /// * `parser` - A mutable reference to a configured tree-sitter parser
+    /// * `options` - The extraction options
+    /// * `namespace_name` - The name of the namespace containing the symbol (e.g. `crate::text`)
+    /// * `symbol_name` - The name of the symbol within the namespace (e.g. `TextFormatter`)
+    ///
+    /// # Returns
+    /// The symbol, if found, or an `ExtractionError` if extraction fails
+    fn get_symbol(
+        &self,
+        metadata: &LibraryMetadata<EntryPoint>,
+        parser: &mut Parser,
+        options: &ExtractorOptions,
+        namespace_name: &str,
+        symbol_name: &str,
+    ) -> Result<Option<Symbol>, ExtractionError> {
+        let report = self.extract_public_api(metadata, parser, options)?;
+        Ok(report
+            .namespaces
+            .iter()
+            .find(|namespace| namespace.name == namespace_name)
+            .and_then(|namespace| namespace.get_symbol(symbol_name))
+            .cloned())
+    }
 
     /// Resolve the path to a dependency
     fn resolve_dependency_path(
@@ -29,3 +90,118 @@ pub trait Extractor<EntryPoint> {
         dependant_path: &Path,
     ) -> Result<PathBuf, DependencyResolutionError>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsing::fixtures::MINIMAL_LANGUAGE;
+    use crate::types::fixtures::{make_namespace, make_symbol};
+    use crate::types::{Namespace, SymbolKind};
+    use assertables::{assert_none, assert_some};
+
+    struct FakeExtractor {
+        namespaces: Vec<Namespace>,
+    }
+
+    impl Extractor<PathBuf> for FakeExtractor {
+        fn get_parser_language(&self) -> Language {
+            unsafe { Language::from_raw(&MINIMAL_LANGUAGE as *const _ as *const _) }
+        }
+
+        fn get_library_metadata(
+            &self,
+            _path: &Path,
+        ) -> Result<LibraryMetadata<PathBuf>, LibraryMetadataError> {
+            Err(LibraryMetadataError::MalformedManifest(
+                "The fake extractor has no manifest".to_string(),
+            ))
+        }
+
+        fn extract_public_api(
+            &self,
+            _metadata: &LibraryMetadata<PathBuf>,
+            _parser: &mut Parser,
+            _options: &ExtractorOptions,
+        ) -> Result<ExtractionReport, ExtractionError> {
+            Ok(ExtractionReport {
+                namespaces: self.namespaces.clone(),
+                warnings: vec![],
+            })
+        }
+
+        fn resolve_dependency_path(
+            &self,
+            _dependency_name: &str,
+            _dependant_path: &Path,
+        ) -> Result<PathBuf, DependencyResolutionError> {
+            Err(DependencyResolutionError::RetrievalFailure(
+                "The fake extractor has no dependencies".to_string(),
+            ))
+        }
+    }
+
+    fn make_extractor() -> FakeExtractor {
+        FakeExtractor {
+            namespaces: vec![make_namespace(
+                "crate::text",
+                vec![Symbol {
+                    kind: SymbolKind::Type,
+                    ..make_symbol("TextFormatter", "pub struct TextFormatter;")
+                }],
+            )],
+        }
+    }
+
+    fn make_metadata() -> LibraryMetadata<PathBuf> {
+        LibraryMetadata::new("crate", PathBuf::from("src/lib.rs"))
+    }
+
+    #[test]
+    fn get_symbol_found() {
+        let extractor = make_extractor();
+
+        let symbol = extractor
+            .get_symbol(
+                &make_metadata(),
+                &mut Parser::new(),
+                &ExtractorOptions::default(),
+                "crate::text",
+                "TextFormatter",
+            )
+            .unwrap();
+
+        assert_some!(&symbol);
+        assert_eq!(symbol.unwrap().name, "TextFormatter");
+    }
+
+    #[test]
+    fn extract_from_sources_unsupported_by_default() {
+        let extractor = make_extractor();
+
+        let result = extractor.extract_from_sources(
+            &[],
+            &make_metadata(),
+            &mut Parser::new(),
+            &ExtractorOptions::default(),
+        );
+
+        assert!(matches!(result, Err(ExtractionError::Unsupported(_))));
+    }
+
+    #[test]
+    fn get_symbol_wrong_namespace() {
+        let extractor = make_extractor();
+
+        let symbol = extractor
+            .get_symbol(
+                &make_metadata(),
+                &mut Parser::new(),
+                &ExtractorOptions::default(),
+                "crate",
+                "TextFormatter",
+            )
+            .unwrap();
+
+        assert_none!(symbol);
+    }
+}
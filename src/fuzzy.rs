@@ -0,0 +1,111 @@
+/// Compute the Levenshtein distance between two strings, counting characters rather than bytes.
+pub(crate) fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous_row: Vec<usize> = (0..=b_chars.len()).collect();
+
+    for (a_index, a_char) in a.chars().enumerate() {
+        let mut current_row = vec![a_index + 1; b_chars.len() + 1];
+        for (b_index, b_char) in b_chars.iter().enumerate() {
+            let substitution_cost = usize::from(a_char != *b_char);
+            current_row[b_index + 1] = (previous_row[b_index] + substitution_cost)
+                .min(previous_row[b_index + 1] + 1)
+                .min(current_row[b_index] + 1);
+        }
+        previous_row = current_row;
+    }
+
+    previous_row[b_chars.len()]
+}
+
+/// The minimum length of a name to be suggested just because it contains or is contained in
+/// another, so that short names like `a` don't match everything.
+const MIN_SUBSTRING_MATCH_LENGTH: usize = 3;
+
+/// Return the candidates similar to `name`, most similar first.
+///
+/// Comparisons ignore case and treat hyphens as underscores, since package and module names
+/// often differ only in that respect. Empty candidates are never suggested.
+pub(crate) fn suggest<'a, I>(name: &str, candidates: I) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let normalised_name = normalise_name(name);
+    let max_distance = (normalised_name.chars().count() / 3).max(1);
+
+    let mut suggestions: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .filter(|candidate| !candidate.is_empty())
+        .filter_map(|candidate| {
+            let normalised_candidate = normalise_name(candidate);
+            let distance = levenshtein_distance(&normalised_name, &normalised_candidate);
+            let is_substring_match = normalised_name.chars().count() >= MIN_SUBSTRING_MATCH_LENGTH
+                && normalised_candidate.chars().count() >= MIN_SUBSTRING_MATCH_LENGTH
+                && (normalised_candidate.contains(&normalised_name)
+                    || normalised_name.contains(&normalised_candidate));
+            let is_similar = distance <= max_distance || is_substring_match;
+            is_similar.then_some((distance, candidate))
+        })
+        .collect();
+    suggestions.sort();
+    suggestions.dedup_by(|a, b| a.1 == b.1);
+    suggestions
+        .into_iter()
+        .map(|(_, candidate)| candidate.to_string())
+        .collect()
+}
+
+pub(crate) fn normalise_name(name: &str) -> String {
+    name.to_lowercase().replace('-', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levenshtein_distance_identical() {
+        assert_eq!(levenshtein_distance("serde", "serde"), 0);
+    }
+
+    #[test]
+    fn levenshtein_distance_edits() {
+        assert_eq!(levenshtein_distance("serde", "serd"), 1);
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+        assert_eq!(levenshtein_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn suggest_similar_names_first() {
+        let suggestions = suggest("serde_jsn", ["tokio", "serde", "serde_json"]);
+
+        assert_eq!(suggestions, vec!["serde_json", "serde"]);
+    }
+
+    #[test]
+    fn suggest_ignores_case_and_hyphens() {
+        let suggestions = suggest("Serde-Json", ["serde_json"]);
+
+        assert_eq!(suggestions, vec!["serde_json"]);
+    }
+
+    #[test]
+    fn suggest_ignores_empty_candidates() {
+        let suggestions = suggest("serde", ["", "tokio"]);
+
+        assert!(suggestions.is_empty());
+    }
+
+    #[test]
+    fn suggest_ignores_short_substrings() {
+        let suggestions = suggest("serde_json", ["de", "json"]);
+
+        assert_eq!(suggestions, vec!["json"]);
+    }
+
+    #[test]
+    fn suggest_nothing_similar() {
+        let suggestions = suggest("serde", ["tokio", "rand"]);
+
+        assert!(suggestions.is_empty());
+    }
+}
@@ -0,0 +1,76 @@
+use crate::source_code::SourceCode;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Deduplicate file paths and source code shared by many symbols.
+///
+/// Extractors can pass every path and source code through the same interner whilst building
+/// namespaces, so that symbols appearing in several namespaces (e.g. via re-exports) share a
+/// single allocation.
+#[derive(Debug, Default)]
+pub struct Interner {
+    paths: HashSet<Arc<Path>>,
+    source_codes: HashSet<SourceCode>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the shared copy of a path, creating it if necessary.
+    pub fn path(&mut self, path: &Path) -> Arc<Path> {
+        if let Some(interned) = self.paths.get(path) {
+            return Arc::clone(interned);
+        }
+        let interned: Arc<Path> = Arc::from(path);
+        self.paths.insert(Arc::clone(&interned));
+        interned
+    }
+
+    /// Return the shared copy of some source code, keeping this one if it's the first.
+    pub fn source_code(&mut self, source_code: SourceCode) -> SourceCode {
+        if let Some(interned) = self.source_codes.get(&source_code) {
+            return interned.clone();
+        }
+        self.source_codes.insert(source_code.clone());
+        source_code
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_is_shared() {
+        let mut interner = Interner::new();
+
+        let first = interner.path(Path::new("src/lib.rs"));
+        let second = interner.path(Path::new("src/lib.rs"));
+
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn different_paths_are_distinct() {
+        let mut interner = Interner::new();
+
+        let first = interner.path(Path::new("src/lib.rs"));
+        let second = interner.path(Path::new("src/main.rs"));
+
+        assert!(!Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn source_code_is_shared() {
+        let mut interner = Interner::new();
+        let original = interner.source_code(SourceCode::from("pub fn foo() {}"));
+
+        let duplicate = interner.source_code(SourceCode::from("pub fn foo() {}"));
+
+        assert_eq!(duplicate, original);
+        assert!(std::ptr::eq(duplicate.as_str(), original.as_str()));
+    }
+}
@@ -1,13 +1,45 @@
+mod api_diff;
+mod cancellation;
+mod doc_comments;
 mod error;
 mod extractor;
+mod fuzzy;
+mod interner;
 mod library_metadata;
+mod namespace_filter;
+mod options;
 mod parsing;
+mod readme;
+mod search;
+mod source_code;
+mod source_discovery;
+mod sources;
+mod statistics;
 mod tree_sitter_helpers;
 mod types;
+mod visitor;
 
-pub use error::{DependencyResolutionError, ExtractionError};
+pub use api_diff::{
+    diff_public_api, diff_public_api_with, ApiDiff, ClassifiedChange, SemverReport, SymbolChange,
+    VersionBump,
+};
+pub use cancellation::CancellationToken;
+pub use doc_comments::normalise_doc_comment;
+pub use error::{DependencyResolutionError, ExtractionError, SourceDiagnostic};
 pub use extractor::Extractor;
-pub use library_metadata::{LibraryMetadata, LibraryMetadataError};
-pub use parsing::{get_parser, ParserError};
-pub use tree_sitter_helpers::ParsedFile;
-pub use types::{Namespace, Symbol};
+pub use interner::Interner;
+pub use library_metadata::{LibraryFeature, LibraryMetadata, LibraryMetadataError, MetadataValue};
+pub use namespace_filter::NamespaceFilter;
+pub use options::ExtractorOptions;
+pub use parsing::{get_parser, with_parser, ParserError};
+pub use readme::{clean_readme, rewrite_relative_links, ReadmeCleaning};
+pub use search::{search, MatchQuality, SymbolMatch};
+pub use source_code::SourceCode;
+pub use source_discovery::{discover_source_files, DEFAULT_IGNORED_DIRECTORIES};
+pub use sources::{FileSystemSources, InMemorySources, SourceProvider};
+pub use statistics::{compute_statistics, ApiStatistics, SymbolSize};
+pub use tree_sitter_helpers::{normalise_source_code, ParsedFile};
+pub use types::{
+    ExtractionReport, ExtractionWarning, Namespace, Symbol, SymbolKind, SymbolOrder, SymbolVariant,
+};
+pub use visitor::{walk, SymbolVisitor};
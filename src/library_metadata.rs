@@ -1,9 +1,13 @@
+use std::collections::BTreeMap;
 use thiserror::Error;
 
 /// Metadata about a library.
 ///
 /// The metadata is typically extracted from a library's manifest file (e.g., `package.json`, `Cargo.toml`).
+/// Use [`LibraryMetadata::new`] and then set the fields known from the manifest.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub struct LibraryMetadata<EntryPoint> {
     /// The name of the library as specified in its manifest
     pub name: String,
@@ -11,6 +15,37 @@ pub struct LibraryMetadata<EntryPoint> {
     /// The version of the library, if specified in its manifest
     pub version: Option<String>,
 
+    /// A short description of the library, if specified in its manifest
+    pub description: Option<String>,
+
+    /// The license of the library (e.g. an SPDX expression), if specified in its manifest
+    pub license: Option<String>,
+
+    /// The URL of the library's source repository, if specified in its manifest
+    pub repository: Option<String>,
+
+    /// The URL of the library's homepage, if specified in its manifest
+    pub homepage: Option<String>,
+
+    /// The URL of the library's hosted documentation, if specified in its manifest
+    pub documentation_url: Option<String>,
+
+    /// The keywords associated with the library in its manifest
+    pub keywords: Vec<String>,
+
+    /// The categories the library belongs to according to its manifest
+    pub categories: Vec<String>,
+
+    /// The minimum version of the language supported by the library, if specified in its manifest
+    /// (e.g. Rust's `rust-version`, Python's `requires-python`)
+    pub language_version: Option<String>,
+
+    /// The optional features the library offers (e.g. Cargo features, Python extras)
+    pub features: Vec<LibraryFeature>,
+
+    /// Language-specific metadata that has no dedicated field (e.g. Python classifiers, npm tags)
+    pub extra: BTreeMap<String, MetadataValue>,
+
     /// Documentation string for the library, typically extracted from its manifest or documentation files
     pub documentation: String,
 
@@ -21,6 +56,92 @@ pub struct LibraryMetadata<EntryPoint> {
     pub entry_point: EntryPoint,
 }
 
+impl<EntryPoint> LibraryMetadata<EntryPoint> {
+    /// Create the metadata of a library, leaving the optional fields empty.
+    ///
+    /// # Parameters
+    /// * `name` - The name of the library as specified in its manifest
+    /// * `entry_point` - The entry point(s) for the library
+    ///
+    /// # Returns
+    /// The metadata, without a version, documentation or any other optional information
+    pub fn new(name: impl Into<String>, entry_point: EntryPoint) -> Self {
+        Self {
+            name: name.into(),
+            version: None,
+            description: None,
+            license: None,
+            repository: None,
+            homepage: None,
+            documentation_url: None,
+            keywords: Vec::new(),
+            categories: Vec::new(),
+            language_version: None,
+            features: Vec::new(),
+            extra: BTreeMap::new(),
+            documentation: String::new(),
+            entry_point,
+        }
+    }
+}
+
+/// An optional feature of a library, as declared in its manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LibraryFeature {
+    /// The name of the feature
+    pub name: String,
+
+    /// The features and optional dependencies enabled by this feature
+    pub enables: Vec<String>,
+
+    /// Documentation for the feature, if any (e.g. `## ` comments in `Cargo.toml`)
+    pub documentation: Option<String>,
+}
+
+/// A language-specific metadata value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+#[non_exhaustive]
+pub enum MetadataValue {
+    Boolean(bool),
+    Integer(i64),
+    String(String),
+    List(Vec<MetadataValue>),
+    Map(BTreeMap<String, MetadataValue>),
+}
+
+impl From<bool> for MetadataValue {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+impl From<i64> for MetadataValue {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<String> for MetadataValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for MetadataValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl<T: Into<MetadataValue>> From<Vec<T>> for MetadataValue {
+    fn from(values: Vec<T>) -> Self {
+        Self::List(values.into_iter().map(Into::into).collect())
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum LibraryMetadataError {
     #[error(transparent)]
@@ -28,3 +149,52 @@ pub enum LibraryMetadataError {
     #[error("{0}")]
     MalformedManifest(String),
 }
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for LibraryMetadataError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_metadata_without_optional_fields() {
+        let metadata = LibraryMetadata::new("my_lib", "src/lib.rs");
+
+        assert_eq!(metadata.name, "my_lib");
+        assert_eq!(metadata.entry_point, "src/lib.rs");
+        assert_eq!(metadata.version, None);
+        assert!(metadata.features.is_empty());
+        assert!(metadata.documentation.is_empty());
+    }
+
+    #[test]
+    fn metadata_value_from_list() {
+        let value = MetadataValue::from(vec!["web", "async"]);
+
+        assert_eq!(
+            value,
+            MetadataValue::List(vec![
+                MetadataValue::String("web".to_string()),
+                MetadataValue::String("async".to_string()),
+            ])
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn metadata_value_serialises_untagged() {
+        let value = MetadataValue::Map(BTreeMap::from([
+            ("private".to_string(), MetadataValue::from(false)),
+            ("tags".to_string(), MetadataValue::from(vec!["latest"])),
+        ]));
+
+        let json = serde_json::to_string(&value).unwrap();
+
+        assert_eq!(json, r#"{"private":false,"tags":["latest"]}"#);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::types::Namespace;
+
+/// Select namespaces by name using glob patterns, where `*` matches any sequence of characters.
+///
+/// A namespace is selected if it matches any of the `only` patterns (or there are none)
+/// and it matches none of the `exclude` patterns.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct NamespaceFilter {
+    pub only: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl NamespaceFilter {
+    /// Check whether a namespace name is selected by the filter.
+    ///
+    /// # Parameters
+    /// * `namespace_name` - The name of the namespace (e.g. `tokio::net`)
+    ///
+    /// # Returns
+    /// Whether the namespace should be kept
+    pub fn matches(&self, namespace_name: &str) -> bool {
+        let is_included = self.only.is_empty()
+            || self
+                .only
+                .iter()
+                .any(|pattern| glob_matches(pattern, namespace_name));
+        is_included
+            && !self
+                .exclude
+                .iter()
+                .any(|pattern| glob_matches(pattern, namespace_name))
+    }
+
+    /// Remove the namespaces that aren't selected by the filter.
+    ///
+    /// # Parameters
+    /// * `namespaces` - The namespaces to filter
+    ///
+    /// # Returns
+    /// The selected namespaces, in their original order
+    pub fn apply(&self, namespaces: Vec<Namespace>) -> Vec<Namespace> {
+        namespaces
+            .into_iter()
+            .filter(|namespace| self.matches(&namespace.name))
+            .collect()
+    }
+}
+
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut pattern_index, mut text_index) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while text_index < text.len() {
+        if pattern_index < pattern.len() && pattern[pattern_index] == '*' {
+            backtrack = Some((pattern_index, text_index));
+            pattern_index += 1;
+        } else if pattern_index < pattern.len() && pattern[pattern_index] == text[text_index] {
+            pattern_index += 1;
+            text_index += 1;
+        } else if let Some((star_index, star_text_index)) = backtrack {
+            pattern_index = star_index + 1;
+            text_index = star_text_index + 1;
+            backtrack = Some((star_index, star_text_index + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[pattern_index..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::fixtures::make_namespace;
+
+    #[test]
+    fn glob_exact() {
+        assert!(glob_matches("tokio::net", "tokio::net"));
+        assert!(!glob_matches("tokio::net", "tokio::net::tcp"));
+    }
+
+    #[test]
+    fn glob_wildcards() {
+        assert!(glob_matches("tokio::net::*", "tokio::net::tcp"));
+        assert!(glob_matches("*::internal", "tokio::io::internal"));
+        assert!(glob_matches("*", "tokio"));
+        assert!(glob_matches("tokio::*::tcp", "tokio::net::tcp"));
+        assert!(!glob_matches("tokio::net::*", "tokio::io"));
+    }
+
+    #[test]
+    fn empty_filter_keeps_everything() {
+        let filter = NamespaceFilter::default();
+
+        assert!(filter.matches("tokio"));
+    }
+
+    #[test]
+    fn apply_only_and_exclude() {
+        let filter = NamespaceFilter {
+            only: vec!["tokio::net*".to_string()],
+            exclude: vec!["*::internal".to_string()],
+        };
+        let namespaces = vec![
+            make_namespace("tokio", vec![]),
+            make_namespace("tokio::net", vec![]),
+            make_namespace("tokio::net::tcp", vec![]),
+            make_namespace("tokio::net::internal", vec![]),
+        ];
+
+        let filtered = filter.apply(namespaces);
+
+        let names: Vec<&str> = filtered.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["tokio::net", "tokio::net::tcp"]);
+    }
+}
@@ -0,0 +1,246 @@
+use crate::cancellation::CancellationToken;
+use crate::source_discovery::DEFAULT_IGNORED_DIRECTORIES;
+use std::hash::{Hash, Hasher};
+
+/// Options controlling what an extractor includes in the public API and how it renders it.
+///
+/// The defaults reproduce the standard behaviour: only public items are extracted, including
+/// deprecated ones, with no depth limit and the source code kept as-is.
+///
+/// Equality and hashing ignore the cancellation token, so that options can be compared and used
+/// as cache keys. Fields missing when deserialising take their default values.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct ExtractorOptions {
+    /// Whether to include items that aren't part of the public API
+    pub include_private: bool,
+
+    /// Whether to include deprecated items
+    pub include_deprecated: bool,
+
+    /// The maximum namespace nesting depth to extract, if any
+    pub max_depth: Option<usize>,
+
+    /// The features (e.g. Cargo features, Python extras) to consider enabled
+    pub features: Vec<String>,
+
+    /// Whether to convert doc comments to plain Markdown with [`crate::normalise_doc_comment`]
+    pub normalise_doc_comments: bool,
+
+    /// Whether to emit signatures only, omitting bodies and doc comments
+    pub signature_only: bool,
+
+    /// Whether to reformat the extracted source code consistently (e.g. with `prettyplease` for
+    /// Rust), instead of keeping its original formatting
+    pub reformat_signatures: bool,
+
+    /// The names of the directories to skip whilst discovering source files
+    pub ignored_directories: Vec<String>,
+
+    /// The token to check for cancellation requests
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub cancellation_token: CancellationToken,
+}
+
+impl Default for ExtractorOptions {
+    fn default() -> Self {
+        Self {
+            include_private: false,
+            include_deprecated: true,
+            max_depth: None,
+            features: Vec::new(),
+            normalise_doc_comments: false,
+            signature_only: false,
+            reformat_signatures: false,
+            ignored_directories: DEFAULT_IGNORED_DIRECTORIES
+                .iter()
+                .map(|directory| directory.to_string())
+                .collect(),
+            cancellation_token: CancellationToken::new(),
+        }
+    }
+}
+
+/// The fields of [`ExtractorOptions`] that equality and hashing are based on.
+type ComparedFields<'a> = (
+    bool,
+    bool,
+    Option<usize>,
+    &'a [String],
+    bool,
+    bool,
+    bool,
+    &'a [String],
+);
+
+impl PartialEq for ExtractorOptions {
+    fn eq(&self, other: &Self) -> bool {
+        self.compared_fields() == other.compared_fields()
+    }
+}
+
+impl Eq for ExtractorOptions {}
+
+impl Hash for ExtractorOptions {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.compared_fields().hash(state);
+    }
+}
+
+impl ExtractorOptions {
+    /// Create options with the default behaviour.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether to include items that aren't part of the public API.
+    pub fn with_private(mut self, include_private: bool) -> Self {
+        self.include_private = include_private;
+        self
+    }
+
+    /// Set whether to include deprecated items.
+    pub fn with_deprecated(mut self, include_deprecated: bool) -> Self {
+        self.include_deprecated = include_deprecated;
+        self
+    }
+
+    /// Limit the namespace nesting depth to extract.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Set the features to consider enabled.
+    pub fn with_features<I, S>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.features = features.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set whether to convert doc comments to plain Markdown.
+    pub fn with_normalised_doc_comments(mut self, normalise_doc_comments: bool) -> Self {
+        self.normalise_doc_comments = normalise_doc_comments;
+        self
+    }
+
+    /// Set whether to emit signatures only.
+    pub fn with_signature_only(mut self, signature_only: bool) -> Self {
+        self.signature_only = signature_only;
+        self
+    }
+
+    /// Set whether to reformat the extracted source code consistently.
+    pub fn with_reformatted_signatures(mut self, reformat_signatures: bool) -> Self {
+        self.reformat_signatures = reformat_signatures;
+        self
+    }
+
+    /// Set the token to check for cancellation requests.
+    pub fn with_cancellation_token(mut self, cancellation_token: CancellationToken) -> Self {
+        self.cancellation_token = cancellation_token;
+        self
+    }
+
+    /// Set the names of the directories to skip whilst discovering source files.
+    pub fn with_ignored_directories<I, S>(mut self, ignored_directories: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.ignored_directories = ignored_directories.into_iter().map(Into::into).collect();
+        self
+    }
+
+    fn compared_fields(&self) -> ComparedFields<'_> {
+        let Self {
+            include_private,
+            include_deprecated,
+            max_depth,
+            features,
+            normalise_doc_comments,
+            signature_only,
+            reformat_signatures,
+            ignored_directories,
+            cancellation_token: _,
+        } = self;
+        (
+            *include_private,
+            *include_deprecated,
+            *max_depth,
+            features,
+            *normalise_doc_comments,
+            *signature_only,
+            *reformat_signatures,
+            ignored_directories,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults() {
+        let options = ExtractorOptions::new();
+
+        assert!(!options.include_private);
+        assert!(options.include_deprecated);
+        assert_eq!(options.max_depth, None);
+        assert!(options.features.is_empty());
+        assert!(!options.normalise_doc_comments);
+        assert!(!options.signature_only);
+        assert!(!options.reformat_signatures);
+        assert_eq!(options.ignored_directories, DEFAULT_IGNORED_DIRECTORIES);
+        assert!(!options.cancellation_token.is_cancelled());
+    }
+
+    #[test]
+    fn equality_ignores_cancellation_token() {
+        assert_eq!(ExtractorOptions::new(), ExtractorOptions::new());
+        assert_ne!(
+            ExtractorOptions::new(),
+            ExtractorOptions::new().with_private(true)
+        );
+    }
+
+    #[test]
+    fn builder() {
+        let cancellation_token = CancellationToken::new();
+        let options = ExtractorOptions::new()
+            .with_private(true)
+            .with_deprecated(false)
+            .with_max_depth(3)
+            .with_features(["rt", "net"])
+            .with_normalised_doc_comments(true)
+            .with_signature_only(true)
+            .with_reformatted_signatures(true)
+            .with_ignored_directories(["generated"])
+            .with_cancellation_token(cancellation_token.clone());
+
+        assert!(options.include_private);
+        assert!(!options.include_deprecated);
+        assert_eq!(options.max_depth, Some(3));
+        assert_eq!(options.features, vec!["rt", "net"]);
+        assert!(options.normalise_doc_comments);
+        assert!(options.signature_only);
+        assert!(options.reformat_signatures);
+        assert_eq!(options.ignored_directories, vec!["generated"]);
+        assert_eq!(options.cancellation_token, cancellation_token);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialise_with_missing_fields() {
+        let options: ExtractorOptions =
+            serde_json::from_str(r#"{"include_private": true}"#).unwrap();
+
+        assert_eq!(options, ExtractorOptions::new().with_private(true));
+    }
+}
@@ -1,24 +1,82 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
 use thiserror::Error;
 use tree_sitter::{Language, LanguageError, Parser};
 
+thread_local! {
+    static PARSER_POOL: RefCell<HashMap<Language, Vec<Parser>>> = RefCell::new(HashMap::new());
+}
+
 #[derive(Error, Debug)]
 #[error(transparent)]
 pub struct ParserError(#[from] LanguageError);
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for ParserError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
 pub fn get_parser(parser_language: &Language) -> Result<Parser, ParserError> {
     let mut parser = Parser::new();
     parser.set_language(parser_language).map_err(ParserError)?;
     Ok(parser)
 }
 
+/// Run a function with a parser for the given language.
+///
+/// Parsers are pooled per thread, so a parser configured by a previous call on the same thread
+/// is reused instead of creating a new one. If the function changes the parser's language, the
+/// parser is discarded instead of being returned to the pool.
+///
+/// # Parameters
+/// * `parser_language` - The tree-sitter language to parse
+/// * `f` - The function to run with the parser
+///
+/// # Returns
+/// The result of the function or a `ParserError` if a new parser could not be configured
+pub fn with_parser<T>(
+    parser_language: &Language,
+    f: impl FnOnce(&mut Parser) -> T,
+) -> Result<T, ParserError> {
+    let pooled_parser = PARSER_POOL.with(|pool| {
+        pool.borrow_mut()
+            .get_mut(parser_language)
+            .and_then(Vec::pop)
+    });
+    let mut parser = match pooled_parser {
+        Some(parser) => parser,
+        None => get_parser(parser_language)?,
+    };
+
+    let result = f(&mut parser);
+
+    let has_original_language = parser
+        .language()
+        .is_some_and(|language| *language == *parser_language);
+    if has_original_language {
+        parser.reset();
+        PARSER_POOL.with(|pool| {
+            pool.borrow_mut()
+                .entry(parser_language.clone())
+                .or_default()
+                .push(parser)
+        });
+    }
+    Ok(result)
+}
+
+/// Minimal tree-sitter languages for tests across the crate that need a language but don't parse.
+///
+/// Use them with `unsafe { Language::from_raw(&MINIMAL_LANGUAGE as *const _ as *const _) }`.
 #[cfg(test)]
-mod tests {
-    use super::*;
+pub(crate) mod fixtures {
     use std::ffi::c_void;
 
     // Minimal valid language struct matching tree-sitter's TSLanguage
     #[repr(C)]
-    struct MinimalLanguage {
+    pub(crate) struct MinimalLanguage {
         version: u32,
         symbol_count: u32,
         symbol_metadata: &'static [u32],
@@ -33,7 +91,7 @@ mod tests {
         lex_fn: Option<unsafe extern "C" fn(*mut c_void, u32, *mut c_void) -> bool>,
     }
 
-    static MINIMAL_LANGUAGE: MinimalLanguage = MinimalLanguage {
+    const MINIMAL_LANGUAGE_DEFINITION: MinimalLanguage = MinimalLanguage {
         version: 14, // TREE_SITTER_LANGUAGE_VERSION
         symbol_count: 1,
         symbol_metadata: &[0],
@@ -48,6 +106,17 @@ mod tests {
         lex_fn: None,
     };
 
+    pub(crate) static MINIMAL_LANGUAGE: MinimalLanguage = MINIMAL_LANGUAGE_DEFINITION;
+
+    // A distinct language with the same definition, for tests involving two languages
+    pub(crate) static OTHER_MINIMAL_LANGUAGE: MinimalLanguage = MINIMAL_LANGUAGE_DEFINITION;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::{MINIMAL_LANGUAGE, OTHER_MINIMAL_LANGUAGE};
+    use super::*;
+
     #[test]
     fn get_parser_valid() {
         let language = unsafe { Language::from_raw(&MINIMAL_LANGUAGE as *const _ as *const _) };
@@ -56,4 +125,43 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    fn count_pooled_parsers(language: &Language) -> usize {
+        PARSER_POOL.with(|pool| pool.borrow().get(language).map_or(0, Vec::len))
+    }
+
+    #[test]
+    fn with_parser_reuses_parser() {
+        let language = unsafe { Language::from_raw(&MINIMAL_LANGUAGE as *const _ as *const _) };
+
+        with_parser(&language, |_| ()).unwrap();
+        with_parser(&language, |_| ()).unwrap();
+
+        assert_eq!(count_pooled_parsers(&language), 1);
+    }
+
+    #[test]
+    fn with_parser_nested() {
+        let language = unsafe { Language::from_raw(&MINIMAL_LANGUAGE as *const _ as *const _) };
+
+        let result = with_parser(&language, |_| with_parser(&language, |_| 42).unwrap()).unwrap();
+
+        assert_eq!(result, 42);
+        assert_eq!(count_pooled_parsers(&language), 2);
+    }
+
+    #[test]
+    fn with_parser_discards_parser_with_changed_language() {
+        let language = unsafe { Language::from_raw(&MINIMAL_LANGUAGE as *const _ as *const _) };
+        let other_language =
+            unsafe { Language::from_raw(&OTHER_MINIMAL_LANGUAGE as *const _ as *const _) };
+
+        with_parser(&language, |parser| {
+            parser.set_language(&other_language).unwrap();
+        })
+        .unwrap();
+
+        assert_eq!(count_pooled_parsers(&language), 0);
+        assert_eq!(count_pooled_parsers(&other_language), 0);
+    }
 }
@@ -0,0 +1,590 @@
+/// What to remove from a README before using it as a library's documentation.
+///
+/// Start from [`ReadmeCleaning::default`], which removes everything, and turn off what to keep.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct ReadmeCleaning {
+    /// Whether to remove lines consisting only of badge images
+    pub remove_badges: bool,
+
+    /// Whether to remove raw HTML tags, keeping any text they wrap
+    pub remove_html: bool,
+
+    /// Whether to remove a leading title that merely repeats the library name
+    pub remove_title: bool,
+}
+
+impl Default for ReadmeCleaning {
+    fn default() -> Self {
+        Self {
+            remove_badges: true,
+            remove_html: true,
+            remove_title: true,
+        }
+    }
+}
+
+/// Remove noise such as badges and HTML wrappers from a README.
+///
+/// Fenced code blocks are left untouched, and the blank lines left behind by removed content
+/// are collapsed.
+///
+/// # Parameters
+/// * `readme` - The Markdown content of the README
+/// * `library_name` - The name of the library, used to detect a redundant title
+/// * `cleaning` - What to remove
+///
+/// # Returns
+/// The cleaned README
+pub fn clean_readme(readme: &str, library_name: &str, cleaning: &ReadmeCleaning) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut is_in_code_block = false;
+    let mut has_content = false;
+
+    for line in readme.lines() {
+        let trimmed = line.trim();
+        if is_code_fence(trimmed) {
+            is_in_code_block = !is_in_code_block;
+        }
+        if is_in_code_block || is_code_fence(trimmed) {
+            lines.push(line.to_string());
+            has_content = true;
+            continue;
+        }
+
+        if cleaning.remove_badges && is_badge_line(trimmed) {
+            continue;
+        }
+        let line = if cleaning.remove_html && trimmed.starts_with('<') {
+            let text = strip_html_tags(trimmed);
+            if text.trim().is_empty() {
+                continue;
+            }
+            text.trim().to_string()
+        } else {
+            line.to_string()
+        };
+        if cleaning.remove_title && !has_content && is_title_of(&line, library_name) {
+            continue;
+        }
+
+        has_content |= !line.trim().is_empty();
+        lines.push(line);
+    }
+
+    collapse_blank_lines(&lines)
+}
+
+/// Rewrite the relative links and image sources in a README as absolute repository URLs.
+///
+/// Markdown links, reference definitions and HTML `href`/`src` attributes are rewritten,
+/// except in fenced code blocks. GitHub and GitLab URLs point at the default branch's file view
+/// (or the raw file for images); any other repository URL is used as the base as-is.
+///
+/// Relative links are resolved against the README's directory, as repository hosts do, whilst
+/// links starting with `/` are resolved against the root of the repository.
+///
+/// # Parameters
+/// * `readme` - The Markdown content of the README
+/// * `repository_url` - The URL of the library's source repository
+/// * `readme_directory` - The directory containing the README within the repository
+///   (e.g. `crates/foo` in a workspace), or an empty string if it's at the root
+///
+/// # Returns
+/// The README with absolute links
+pub fn rewrite_relative_links(
+    readme: &str,
+    repository_url: &str,
+    readme_directory: &str,
+) -> String {
+    let repository_url = repository_url.trim_end_matches('/');
+    let repository_url = repository_url
+        .strip_suffix(".git")
+        .unwrap_or(repository_url);
+    let mut is_in_code_block = false;
+
+    let lines: Vec<String> = readme
+        .lines()
+        .map(|line| {
+            if is_code_fence(line.trim()) {
+                is_in_code_block = !is_in_code_block;
+                return line.to_string();
+            }
+            if is_in_code_block {
+                return line.to_string();
+            }
+            let line = rewrite_link_targets(line, "](", ")", repository_url, readme_directory);
+            let line =
+                rewrite_link_targets(&line, "href=\"", "\"", repository_url, readme_directory);
+            let line =
+                rewrite_link_targets(&line, "src=\"", "\"", repository_url, readme_directory);
+            rewrite_reference_definition(&line, repository_url, readme_directory)
+        })
+        .collect();
+    let mut rewritten = lines.join("\n");
+    if readme.ends_with('\n') {
+        rewritten.push('\n');
+    }
+    rewritten
+}
+
+fn rewrite_link_targets(
+    line: &str,
+    opening: &str,
+    closing: &str,
+    repository_url: &str,
+    readme_directory: &str,
+) -> String {
+    let mut rewritten = String::new();
+    let mut remainder = line;
+    while let Some(start) = remainder.find(opening) {
+        let target_start = start + opening.len();
+        rewritten.push_str(&remainder[..target_start]);
+        remainder = &remainder[target_start..];
+        let Some(end) = remainder.find(closing) else {
+            break;
+        };
+        let (target, suffix) = split_target(&remainder[..end]);
+        rewritten.push_str(&make_absolute(target, repository_url, readme_directory));
+        rewritten.push_str(suffix);
+        remainder = &remainder[end..];
+    }
+    rewritten.push_str(remainder);
+    rewritten
+}
+
+/// Rewrite the target of a link reference definition (e.g. `[guide]: docs/guide.md "Guide"`).
+///
+/// Footnotes (e.g. `[^1]: Text`) and lines whose target isn't a single destination, optionally
+/// followed by a title, are left unchanged.
+fn rewrite_reference_definition(
+    line: &str,
+    repository_url: &str,
+    readme_directory: &str,
+) -> String {
+    let trimmed = line.trim_start();
+    let Some(label_end) = trimmed.find("]:") else {
+        return line.to_string();
+    };
+    let label = &trimmed[..label_end];
+    let is_definition = label.len() > 1
+        && label.starts_with('[')
+        && !label.starts_with("[^")
+        && !label[1..].contains(['[', ']']);
+    if !is_definition {
+        return line.to_string();
+    }
+
+    let after_label = &line[line.len() - trimmed.len() + label_end + 2..];
+    let destination = after_label.trim_start();
+    let mut target_start = line.len() - destination.len();
+    let (target, suffix) = match destination.strip_prefix('<') {
+        Some(bracketed) => {
+            let Some(end) = bracketed.find('>') else {
+                return line.to_string();
+            };
+            target_start += 1;
+            (&bracketed[..end], &bracketed[end + 1..])
+        }
+        None => split_target(destination),
+    };
+    let title = suffix.trim_start();
+    let has_title = suffix.starts_with(char::is_whitespace) && title.starts_with(['"', '\'', '(']);
+    let is_destination = !target.is_empty() && (title.is_empty() || has_title);
+    if !is_destination {
+        return line.to_string();
+    }
+    let target_end = target_start + target.len();
+    format!(
+        "{}{}{}",
+        &line[..target_start],
+        make_absolute(target, repository_url, readme_directory),
+        &line[target_end..]
+    )
+}
+
+/// Split a link target from any title following it (e.g. `guide.md "The guide"`).
+fn split_target(text: &str) -> (&str, &str) {
+    let end = text.find(char::is_whitespace).unwrap_or(text.len());
+    text.split_at(end)
+}
+
+fn make_absolute(target: &str, repository_url: &str, readme_directory: &str) -> String {
+    if !is_relative(target) {
+        return target.to_string();
+    }
+    let path = match target.strip_prefix('/') {
+        Some(path_from_root) => normalise_path(path_from_root),
+        None => normalise_path(&format!("{}/{}", readme_directory, target)),
+    };
+    let path = path.as_str();
+    let view = if is_image(path) { "raw" } else { "blob" };
+    if repository_url.contains("://github.com/") {
+        format!("{}/{}/HEAD/{}", repository_url, view, path)
+    } else if repository_url.contains("://gitlab.com/") {
+        format!("{}/-/{}/HEAD/{}", repository_url, view, path)
+    } else {
+        format!("{}/{}", repository_url, path)
+    }
+}
+
+/// Resolve the `.` and `..` segments of a path within the repository.
+///
+/// `..` segments that would leave the repository are dropped, like repository hosts do.
+fn normalise_path(path: &str) -> String {
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+fn is_relative(target: &str) -> bool {
+    if target.is_empty() || target.starts_with('#') || target.starts_with("//") {
+        return false;
+    }
+    let has_scheme = target.find(':').is_some_and(|colon_index| {
+        let scheme = &target[..colon_index];
+        !scheme.is_empty()
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    });
+    !has_scheme
+}
+
+fn is_image(path: &str) -> bool {
+    let path = path.split(['#', '?']).next().unwrap_or(path).to_lowercase();
+    [".png", ".jpg", ".jpeg", ".gif", ".svg", ".webp"]
+        .iter()
+        .any(|extension| path.ends_with(extension))
+}
+
+fn is_code_fence(line: &str) -> bool {
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+fn is_badge_line(line: &str) -> bool {
+    let mut remainder = line;
+    if remainder.is_empty() {
+        return false;
+    }
+    while !remainder.is_empty() {
+        match strip_badge(remainder) {
+            Some(rest) => remainder = rest.trim_start(),
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Strip a leading image (`![alt](src)`) or linked image (`[![alt](src)](href)`).
+fn strip_badge(text: &str) -> Option<&str> {
+    if let Some(rest) = text.strip_prefix('[') {
+        let rest = strip_image(rest)?;
+        let rest = rest.strip_prefix("](")?;
+        let end = rest.find(')')?;
+        Some(&rest[end + 1..])
+    } else {
+        strip_image(text)
+    }
+}
+
+fn strip_image(text: &str) -> Option<&str> {
+    let rest = text.strip_prefix("![")?;
+    let rest = &rest[rest.find("](")? + 2..];
+    let end = rest.find(')')?;
+    Some(&rest[end + 1..])
+}
+
+/// Remove the HTML tags from a line, keeping the text they wrap and any Markdown autolinks.
+fn strip_html_tags(line: &str) -> String {
+    let mut text = String::new();
+    let mut remainder = line;
+    while let Some(start) = remainder.find('<') {
+        text.push_str(&remainder[..start]);
+        remainder = &remainder[start..];
+        let Some(end) = remainder.find('>') else {
+            // An unclosed tag continues on the next line, so drop the rest of this one
+            return text;
+        };
+        if is_autolink(&remainder[1..end]) {
+            text.push_str(&remainder[..=end]);
+        }
+        remainder = &remainder[end + 1..];
+    }
+    text.push_str(remainder);
+    text
+}
+
+/// Check whether the content of angle brackets is a URI or email autolink
+/// (e.g. `https://docs.rs/foo` or `user@example.com`) rather than an HTML tag.
+fn is_autolink(content: &str) -> bool {
+    if content.is_empty() || content.contains(|c: char| c.is_whitespace() || c == '<') {
+        return false;
+    }
+    let is_uri = content.split_once(':').is_some_and(|(scheme, _)| {
+        (2..=32).contains(&scheme.len())
+            && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    });
+    let is_email = content.split_once('@').is_some_and(|(user, host)| {
+        !user.is_empty()
+            && !host.is_empty()
+            && host
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.'))
+    });
+    is_uri || is_email
+}
+
+fn is_title_of(line: &str, library_name: &str) -> bool {
+    let Some(title) = line.trim().strip_prefix("# ") else {
+        return false;
+    };
+    let normalise = |name: &str| {
+        name.trim_matches(|c: char| c == '`' || c == '_' || c == '*' || c.is_whitespace())
+            .to_lowercase()
+            .replace('-', "_")
+    };
+    normalise(title) == normalise(library_name)
+}
+
+fn collapse_blank_lines(lines: &[String]) -> String {
+    let mut collapsed: Vec<&str> = Vec::new();
+    for line in lines {
+        let is_blank = line.trim().is_empty();
+        let previous_is_blank = collapsed.last().is_none_or(|previous| previous.is_empty());
+        if is_blank && previous_is_blank {
+            continue;
+        }
+        collapsed.push(if is_blank { "" } else { line });
+    }
+    while collapsed.last() == Some(&"") {
+        collapsed.pop();
+    }
+    collapsed.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removes_badges() {
+        let readme = "[![Crates.io](https://img.shields.io/crates/v/foo.svg)](https://crates.io/crates/foo) ![CI](https://ci/badge.svg)\n\nFoo does things.";
+
+        let cleaned = clean_readme(readme, "foo", &ReadmeCleaning::default());
+
+        assert_eq!(cleaned, "Foo does things.");
+    }
+
+    #[test]
+    fn keeps_inline_images_in_prose() {
+        let readme = "See ![diagram](diagram.png) for details.";
+
+        let cleaned = clean_readme(readme, "foo", &ReadmeCleaning::default());
+
+        assert_eq!(cleaned, readme);
+    }
+
+    #[test]
+    fn removes_html_wrappers() {
+        let readme = "<p align=\"center\">\n  <img src=\"logo.png\" />\n</p>\n\n<h2 align=\"center\">Fast things</h2>\n\nProse.";
+
+        let cleaned = clean_readme(readme, "foo", &ReadmeCleaning::default());
+
+        assert_eq!(cleaned, "Fast things\n\nProse.");
+    }
+
+    #[test]
+    fn keeps_autolinks() {
+        let readme = "<https://docs.rs/foo> has the docs.\n\n<user@example.com>\n\n<p>Prose</p>";
+
+        let cleaned = clean_readme(readme, "foo", &ReadmeCleaning::default());
+
+        assert_eq!(
+            cleaned,
+            "<https://docs.rs/foo> has the docs.\n\n<user@example.com>\n\nProse"
+        );
+    }
+
+    #[test]
+    fn removes_redundant_title() {
+        let readme = "# my-lib\n\nProse.\n\n# My_Lib";
+
+        let cleaned = clean_readme(readme, "my_lib", &ReadmeCleaning::default());
+
+        assert_eq!(cleaned, "Prose.\n\n# My_Lib");
+    }
+
+    #[test]
+    fn keeps_descriptive_title() {
+        let readme = "# My library for doing things\n\nProse.";
+
+        let cleaned = clean_readme(readme, "my_lib", &ReadmeCleaning::default());
+
+        assert_eq!(cleaned, readme);
+    }
+
+    #[test]
+    fn leaves_code_blocks_untouched() {
+        let readme = "```html\n<div>\n![x](y)\n</div>\n```";
+
+        let cleaned = clean_readme(readme, "foo", &ReadmeCleaning::default());
+
+        assert_eq!(cleaned, readme);
+    }
+
+    #[test]
+    fn disabled_cleaning() {
+        let readme = "# foo\n\n![CI](https://ci/badge.svg)\n\n<br>";
+        let cleaning = ReadmeCleaning {
+            remove_badges: false,
+            remove_html: false,
+            remove_title: false,
+        };
+
+        let cleaned = clean_readme(readme, "foo", &cleaning);
+
+        assert_eq!(cleaned, readme);
+    }
+
+    const GITHUB_REPOSITORY: &str = "https://github.com/org/repo";
+
+    #[test]
+    fn rewrites_relative_markdown_links() {
+        let readme = "See [the guide](docs/guide.md#usage \"Guide\").";
+
+        let rewritten = rewrite_relative_links(readme, GITHUB_REPOSITORY, "");
+
+        assert_eq!(
+            rewritten,
+            "See [the guide](https://github.com/org/repo/blob/HEAD/docs/guide.md#usage \"Guide\")."
+        );
+    }
+
+    #[test]
+    fn rewrites_relative_images_to_raw_files() {
+        let readme = "![Logo](./assets/logo.png)\n<img src=\"assets/banner.svg\">";
+
+        let rewritten = rewrite_relative_links(readme, GITHUB_REPOSITORY, "");
+
+        assert_eq!(
+            rewritten,
+            "![Logo](https://github.com/org/repo/raw/HEAD/assets/logo.png)\n\
+             <img src=\"https://github.com/org/repo/raw/HEAD/assets/banner.svg\">"
+        );
+    }
+
+    #[test]
+    fn rewrites_reference_definitions() {
+        let readme = "[guide]: /docs/guide.md";
+
+        let rewritten = rewrite_relative_links(readme, "https://gitlab.com/org/repo.git", "");
+
+        assert_eq!(
+            rewritten,
+            "[guide]: https://gitlab.com/org/repo/-/blob/HEAD/docs/guide.md"
+        );
+    }
+
+    #[test]
+    fn rewrites_bracketed_reference_definitions() {
+        let readme = "[guide]: <docs/the guide.md> \"Guide\"";
+
+        let rewritten = rewrite_relative_links(readme, GITHUB_REPOSITORY, "");
+
+        assert_eq!(
+            rewritten,
+            "[guide]: <https://github.com/org/repo/blob/HEAD/docs/the guide.md> \"Guide\""
+        );
+    }
+
+    #[test]
+    fn keeps_footnotes_and_prose() {
+        let readme = "[^1]: The footnote text.\n[note]: this is not a link";
+
+        let rewritten = rewrite_relative_links(readme, GITHUB_REPOSITORY, "");
+
+        assert_eq!(rewritten, readme);
+    }
+
+    #[test]
+    fn keeps_absolute_links_and_anchors() {
+        let readme = "[docs](https://docs.rs/foo) [mail](mailto:a@b.c) [section](#usage) <a href=\"//cdn.example/x\">";
+
+        let rewritten = rewrite_relative_links(readme, GITHUB_REPOSITORY, "");
+
+        assert_eq!(rewritten, readme);
+    }
+
+    #[test]
+    fn uses_other_repository_urls_as_base() {
+        let readme = "[guide](docs/guide.md)";
+
+        let rewritten = rewrite_relative_links(readme, "https://git.example.org/repo/", "");
+
+        assert_eq!(
+            rewritten,
+            "[guide](https://git.example.org/repo/docs/guide.md)"
+        );
+    }
+
+    #[test]
+    fn resolves_links_against_readme_directory() {
+        let readme = "[guide](docs/guide.md) [changelog](/CHANGELOG.md)";
+
+        let rewritten = rewrite_relative_links(readme, GITHUB_REPOSITORY, "crates/foo");
+
+        assert_eq!(
+            rewritten,
+            "[guide](https://github.com/org/repo/blob/HEAD/crates/foo/docs/guide.md) \
+             [changelog](https://github.com/org/repo/blob/HEAD/CHANGELOG.md)"
+        );
+    }
+
+    #[test]
+    fn resolves_parent_directories() {
+        let readme = "[license](../../LICENSE) [bar](../bar/README.md)";
+
+        let rewritten = rewrite_relative_links(readme, GITHUB_REPOSITORY, "crates/foo");
+
+        assert_eq!(
+            rewritten,
+            "[license](https://github.com/org/repo/blob/HEAD/LICENSE) \
+             [bar](https://github.com/org/repo/blob/HEAD/crates/bar/README.md)"
+        );
+    }
+
+    #[test]
+    fn keeps_parent_directories_within_repository() {
+        let readme = "[license](../LICENSE)";
+
+        let rewritten = rewrite_relative_links(readme, GITHUB_REPOSITORY, "");
+
+        assert_eq!(
+            rewritten,
+            "[license](https://github.com/org/repo/blob/HEAD/LICENSE)"
+        );
+    }
+
+    #[test]
+    fn leaves_code_blocks_untouched_when_rewriting() {
+        let readme = "```\n[guide](docs/guide.md)\n```\n";
+
+        let rewritten = rewrite_relative_links(readme, GITHUB_REPOSITORY, "");
+
+        assert_eq!(rewritten, readme);
+    }
+}
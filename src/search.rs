@@ -0,0 +1,149 @@
+use crate::fuzzy::{levenshtein_distance, normalise_name};
+use crate::types::{Namespace, Symbol};
+
+/// How closely a symbol matches a search query, best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum MatchQuality {
+    /// The query is the symbol name or its full path
+    Exact,
+    /// The query is part of the symbol name or its full path
+    Substring,
+    /// The query is within the given edit distance of the symbol name
+    Fuzzy(usize),
+}
+
+/// A symbol found by [`search`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolMatch<'a> {
+    pub namespace: &'a Namespace,
+    pub symbol: &'a Symbol,
+    pub quality: MatchQuality,
+}
+
+impl SymbolMatch<'_> {
+    /// The full path to the symbol (e.g. `tokio::task::spawn_blocking`)
+    pub fn path(&self) -> String {
+        format!("{}::{}", self.namespace.name, self.symbol.name)
+    }
+}
+
+/// Search the public API for symbols resembling a query.
+///
+/// The query is compared against the symbol names and their full paths (e.g. `task::spawn`),
+/// ignoring case and treating hyphens as underscores.
+///
+/// # Parameters
+/// * `namespaces` - The namespaces of the public API
+/// * `query` - The name or partial path to search for
+///
+/// # Returns
+/// The matching symbols, best matches first
+pub fn search<'a>(namespaces: &'a [Namespace], query: &str) -> Vec<SymbolMatch<'a>> {
+    let normalised_query = normalise_name(query);
+    if normalised_query.is_empty() {
+        return vec![];
+    }
+    let max_distance = (normalised_query.chars().count() / 3).max(1);
+
+    let mut matches: Vec<SymbolMatch<'a>> = namespaces
+        .iter()
+        .flat_map(|namespace| {
+            namespace
+                .symbols
+                .iter()
+                .map(move |symbol| (namespace, symbol))
+        })
+        .filter_map(|(namespace, symbol)| {
+            let name = normalise_name(&symbol.name);
+            let path = normalise_name(&format!("{}::{}", namespace.name, symbol.name));
+            let quality = if name == normalised_query || path == normalised_query {
+                MatchQuality::Exact
+            } else if path.contains(&normalised_query) {
+                MatchQuality::Substring
+            } else {
+                let distance = levenshtein_distance(&normalised_query, &name);
+                if distance > max_distance {
+                    return None;
+                }
+                MatchQuality::Fuzzy(distance)
+            };
+            Some(SymbolMatch {
+                namespace,
+                symbol,
+                quality,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| {
+        a.quality
+            .cmp(&b.quality)
+            .then_with(|| a.namespace.name.cmp(&b.namespace.name))
+            .then_with(|| a.symbol.name.cmp(&b.symbol.name))
+    });
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::fixtures::{make_namespace, make_symbol};
+
+    fn make_namespaces() -> Vec<Namespace> {
+        vec![
+            make_namespace("tokio", vec![make_symbol("spawn", "pub fn spawn() {}")]),
+            make_namespace(
+                "tokio::task",
+                vec![
+                    make_symbol("spawn_blocking", "pub fn spawn_blocking() {}"),
+                    make_symbol("yield_now", "pub async fn yield_now() {}"),
+                ],
+            ),
+        ]
+    }
+
+    fn get_paths(matches: &[SymbolMatch]) -> Vec<String> {
+        matches.iter().map(SymbolMatch::path).collect()
+    }
+
+    #[test]
+    fn exact_match_ranks_first() {
+        let namespaces = make_namespaces();
+
+        let matches = search(&namespaces, "Spawn");
+
+        assert_eq!(
+            get_paths(&matches),
+            vec!["tokio::spawn", "tokio::task::spawn_blocking"]
+        );
+        assert_eq!(matches[0].quality, MatchQuality::Exact);
+        assert_eq!(matches[1].quality, MatchQuality::Substring);
+    }
+
+    #[test]
+    fn module_path() {
+        let namespaces = make_namespaces();
+
+        let matches = search(&namespaces, "task::yield");
+
+        assert_eq!(get_paths(&matches), vec!["tokio::task::yield_now"]);
+    }
+
+    #[test]
+    fn fuzzy_match() {
+        let namespaces = make_namespaces();
+
+        let matches = search(&namespaces, "Spwan-Blocking");
+
+        assert_eq!(get_paths(&matches), vec!["tokio::task::spawn_blocking"]);
+        assert_eq!(matches[0].quality, MatchQuality::Fuzzy(2));
+    }
+
+    #[test]
+    fn no_match() {
+        let namespaces = make_namespaces();
+
+        assert!(search(&namespaces, "connect").is_empty());
+        assert!(search(&namespaces, "").is_empty());
+    }
+}
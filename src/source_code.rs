@@ -0,0 +1,181 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, Range};
+use std::sync::Arc;
+
+/// A slice of a source file, sharing the file's contents with every other slice taken from it.
+///
+/// Cloning is cheap, so symbols can be copied across namespaces (e.g. when resolving
+/// re-exports) without duplicating their source code. Equality, ordering and hashing are based
+/// on the text of the slice alone.
+#[derive(Clone)]
+pub struct SourceCode {
+    source: Arc<str>,
+    range: Range<usize>,
+}
+
+impl SourceCode {
+    /// Create a slice of a source file.
+    ///
+    /// # Parameters
+    /// * `source` - The full contents of the source file
+    /// * `range` - The byte range of the slice
+    ///
+    /// # Returns
+    /// The slice, or `None` if the range is out of bounds or not on character boundaries
+    pub fn new(source: Arc<str>, range: Range<usize>) -> Option<Self> {
+        source.get(range.clone())?;
+        Some(Self { source, range })
+    }
+
+    /// Return the text of the slice.
+    pub fn as_str(&self) -> &str {
+        &self.source[self.range.clone()]
+    }
+
+    /// Return the byte range of the slice within the source file.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+impl Deref for SourceCode {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for SourceCode {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<String> for SourceCode {
+    fn from(source: String) -> Self {
+        Self::from(Arc::<str>::from(source))
+    }
+}
+
+impl From<&str> for SourceCode {
+    fn from(source: &str) -> Self {
+        Self::from(Arc::<str>::from(source))
+    }
+}
+
+impl From<Arc<str>> for SourceCode {
+    fn from(source: Arc<str>) -> Self {
+        let range = 0..source.len();
+        Self { source, range }
+    }
+}
+
+impl PartialEq for SourceCode {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for SourceCode {}
+
+impl PartialEq<str> for SourceCode {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for SourceCode {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialOrd for SourceCode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SourceCode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for SourceCode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl fmt::Debug for SourceCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for SourceCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SourceCode {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SourceCode {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assertables::assert_none;
+
+    #[test]
+    fn new_slices_source() {
+        let source: Arc<str> = Arc::from("fn foo() {}\nfn bar() {}");
+
+        let source_code = SourceCode::new(source, 12..23).unwrap();
+
+        assert_eq!(source_code.as_str(), "fn bar() {}");
+        assert_eq!(source_code.range(), 12..23);
+    }
+
+    #[test]
+    fn new_out_of_bounds() {
+        let source: Arc<str> = Arc::from("fn foo() {}");
+
+        let source_code = SourceCode::new(source, 5..50);
+
+        assert_none!(source_code);
+    }
+
+    #[test]
+    fn new_not_on_char_boundary() {
+        let source: Arc<str> = Arc::from("é");
+
+        let source_code = SourceCode::new(source, 0..1);
+
+        assert_none!(source_code);
+    }
+
+    #[test]
+    fn equality_ignores_underlying_source() {
+        let source: Arc<str> = Arc::from("fn foo() {}\nfn foo() {}");
+        let first = SourceCode::new(source.clone(), 0..11).unwrap();
+        let second = SourceCode::new(source, 12..23).unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(first, SourceCode::from("fn foo() {}"));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::ExtractionError;
+use std::path::{Path, PathBuf};
+use walkdir::{DirEntry, WalkDir};
+
+/// Directories skipped by default whilst discovering source files, as they typically contain
+/// build artifacts or installed dependencies rather than the library's own sources.
+pub const DEFAULT_IGNORED_DIRECTORIES: &[&str] = &["target", "node_modules", "__pycache__"];
+
+/// Find the source files under a directory.
+///
+/// Hidden files and directories (e.g. `.git`), editor backup files (e.g. `lib.rs~`) and the
+/// ignored directories are skipped. Symbolic links are not followed.
+///
+/// # Parameters
+/// * `root` - The directory to search
+/// * `extensions` - The extensions of the source files (e.g. `rs`), without the leading dot
+/// * `ignored_directories` - The names of the directories to skip,
+///   typically `ExtractorOptions::ignored_directories`
+///
+/// # Returns
+/// The paths of the source files, sorted, or an `ExtractionError` if a directory can't be read
+pub fn discover_source_files(
+    root: &Path,
+    extensions: &[&str],
+    ignored_directories: &[String],
+) -> Result<Vec<PathBuf>, ExtractionError> {
+    let mut source_files = Vec::new();
+    let walker = WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !is_ignored(entry, ignored_directories));
+    for entry in walker {
+        let entry = entry.map_err(std::io::Error::from)?;
+        let has_source_extension = entry
+            .path()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| extensions.contains(&extension));
+        if entry.file_type().is_file() && has_source_extension {
+            source_files.push(entry.into_path());
+        }
+    }
+    Ok(source_files)
+}
+
+fn is_ignored(entry: &DirEntry, ignored_directories: &[String]) -> bool {
+    let Some(name) = entry.file_name().to_str() else {
+        return false;
+    };
+    if name.starts_with('.') || name.ends_with('~') || name.starts_with('#') {
+        return true;
+    }
+    entry.file_type().is_dir() && ignored_directories.iter().any(|ignored| ignored == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ExtractorOptions;
+    use std::fs;
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!(
+                "daipendency-extractor-{}-{}",
+                name,
+                std::process::id()
+            ));
+            fs::create_dir_all(&path).unwrap();
+            Self(path)
+        }
+
+        fn create_file(&self, relative_path: &str) {
+            let path = self.0.join(relative_path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn finds_source_files_recursively() {
+        let root = TempDir::new("recursive");
+        root.create_file("src/lib.rs");
+        root.create_file("src/text/mod.rs");
+        root.create_file("README.md");
+
+        let files = discover_source_files(
+            &root.0,
+            &["rs"],
+            &ExtractorOptions::default().ignored_directories,
+        )
+        .unwrap();
+
+        assert_eq!(
+            files,
+            vec![root.0.join("src/lib.rs"), root.0.join("src/text/mod.rs")]
+        );
+    }
+
+    #[test]
+    fn skips_hidden_and_backup_files() {
+        let root = TempDir::new("hidden");
+        root.create_file("src/lib.rs");
+        root.create_file(".git/hooks/hook.rs");
+        root.create_file("src/lib.rs~");
+        root.create_file("src/#lib.rs#");
+
+        let files = discover_source_files(
+            &root.0,
+            &["rs"],
+            &ExtractorOptions::default().ignored_directories,
+        )
+        .unwrap();
+
+        assert_eq!(files, vec![root.0.join("src/lib.rs")]);
+    }
+
+    #[test]
+    fn skips_ignored_directories() {
+        let root = TempDir::new("ignored");
+        root.create_file("src/lib.rs");
+        root.create_file("target/debug/build/out.rs");
+        root.create_file("generated/bindings.rs");
+
+        let ignored_directories = ["target".to_string(), "generated".to_string()];
+        let files = discover_source_files(&root.0, &["rs"], &ignored_directories).unwrap();
+
+        assert_eq!(files, vec![root.0.join("src/lib.rs")]);
+    }
+
+    #[test]
+    fn missing_root() {
+        let root = std::env::temp_dir().join("daipendency-extractor-nonexistent");
+
+        let result = discover_source_files(&root, &["rs"], &[]);
+
+        assert!(matches!(result, Err(ExtractionError::Io(_))));
+    }
+}
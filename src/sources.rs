@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A set of source files an extractor can read from.
+///
+/// Extractors reading their files through this trait can extract from the filesystem and from
+/// in-memory buffers (e.g. unsaved editor buffers) with the same code.
+pub trait SourceProvider {
+    /// Read the contents of a source file
+    fn read_source(&self, path: &Path) -> io::Result<String>;
+
+    /// Check whether a source file exists
+    fn is_file(&self, path: &Path) -> bool;
+}
+
+/// Source files read from the filesystem.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileSystemSources;
+
+impl SourceProvider for FileSystemSources {
+    fn read_source(&self, path: &Path) -> io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+}
+
+/// Source files held in memory, keyed by path.
+#[derive(Debug, Clone, Default)]
+pub struct InMemorySources {
+    files: HashMap<PathBuf, String>,
+}
+
+impl InMemorySources {
+    pub fn new(files: &[(PathBuf, String)]) -> Self {
+        Self {
+            files: files.iter().cloned().collect(),
+        }
+    }
+}
+
+impl SourceProvider for InMemorySources {
+    fn read_source(&self, path: &Path) -> io::Result<String> {
+        self.files.get(path).cloned().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} is not among the in-memory sources", path.display()),
+            )
+        })
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.contains_key(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_memory_sources_read_existing_file() {
+        let sources =
+            InMemorySources::new(&[(PathBuf::from("src/lib.rs"), "pub fn foo() {}".to_string())]);
+
+        let source_code = sources.read_source(Path::new("src/lib.rs")).unwrap();
+
+        assert!(sources.is_file(Path::new("src/lib.rs")));
+        assert_eq!(source_code, "pub fn foo() {}");
+    }
+
+    #[test]
+    fn in_memory_sources_read_missing_file() {
+        let sources = InMemorySources::default();
+
+        let error = sources.read_source(Path::new("src/lib.rs")).unwrap_err();
+
+        assert!(!sources.is_file(Path::new("src/lib.rs")));
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+}
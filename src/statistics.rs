@@ -0,0 +1,141 @@
+use crate::types::{Namespace, Symbol, SymbolKind};
+use crate::visitor::{walk, SymbolVisitor};
+use std::collections::BTreeMap;
+
+/// The size of a symbol's source code
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SymbolSize {
+    pub namespace: String,
+    pub name: String,
+    /// The size of the source code in bytes, including that of any variants
+    pub size: usize,
+}
+
+/// Summary statistics about an extracted public API
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApiStatistics {
+    pub symbol_count: usize,
+    pub counts_by_kind: BTreeMap<SymbolKind, usize>,
+    pub counts_by_namespace: BTreeMap<String, usize>,
+    /// The largest symbols, largest first
+    pub largest_symbols: Vec<SymbolSize>,
+    /// The total size of the symbols' source code in bytes
+    pub total_size: usize,
+}
+
+/// Compute summary statistics about an extracted public API.
+///
+/// # Parameters
+/// * `namespaces` - The namespaces of the public API
+/// * `largest_symbol_count` - How many of the largest symbols to report
+///
+/// # Returns
+/// The statistics
+pub fn compute_statistics(namespaces: &[Namespace], largest_symbol_count: usize) -> ApiStatistics {
+    let mut visitor = StatisticsVisitor::default();
+    walk(namespaces, &mut visitor);
+
+    let mut statistics = visitor.statistics;
+    statistics.largest_symbols.sort_by(|a, b| {
+        b.size
+            .cmp(&a.size)
+            .then_with(|| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)))
+    });
+    statistics.largest_symbols.truncate(largest_symbol_count);
+    statistics
+}
+
+#[derive(Default)]
+struct StatisticsVisitor {
+    statistics: ApiStatistics,
+}
+
+impl SymbolVisitor for StatisticsVisitor {
+    fn visit_namespace(&mut self, namespace: &Namespace) {
+        self.statistics
+            .counts_by_namespace
+            .insert(namespace.name.clone(), namespace.symbols.len());
+    }
+
+    fn visit_symbol(&mut self, namespace: &Namespace, symbol: &Symbol) {
+        let variants_size: usize = symbol
+            .variants
+            .iter()
+            .map(|variant| variant.source_code.len())
+            .sum();
+        let size = symbol.source_code.len() + variants_size;
+        self.statistics.symbol_count += 1;
+        self.statistics.total_size += size;
+        *self
+            .statistics
+            .counts_by_kind
+            .entry(symbol.kind)
+            .or_default() += 1;
+        self.statistics.largest_symbols.push(SymbolSize {
+            namespace: namespace.name.clone(),
+            name: symbol.name.clone(),
+            size,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::fixtures::{make_namespace, make_symbol};
+
+    fn make_symbol_of_kind(name: &str, source_code: &str, kind: SymbolKind) -> Symbol {
+        Symbol {
+            kind,
+            ..make_symbol(name, source_code)
+        }
+    }
+
+    #[test]
+    fn empty_api() {
+        let statistics = compute_statistics(&[], 5);
+
+        assert_eq!(statistics, ApiStatistics::default());
+    }
+
+    #[test]
+    fn counts_and_sizes() {
+        let namespaces = vec![
+            make_namespace(
+                "crate",
+                vec![
+                    make_symbol("run", "pub fn run() {}"),
+                    make_symbol_of_kind("Config", "pub struct Config;", SymbolKind::Type),
+                ],
+            ),
+            make_namespace(
+                "crate::text",
+                vec![make_symbol(
+                    "format",
+                    "pub fn format(text: &str) -> String { todo!() }",
+                )],
+            ),
+        ];
+
+        let statistics = compute_statistics(&namespaces, 2);
+
+        assert_eq!(statistics.symbol_count, 3);
+        assert_eq!(
+            statistics.counts_by_kind,
+            BTreeMap::from([(SymbolKind::Type, 1), (SymbolKind::Function, 2)])
+        );
+        assert_eq!(
+            statistics.counts_by_namespace,
+            BTreeMap::from([("crate".to_string(), 2), ("crate::text".to_string(), 1)])
+        );
+        assert_eq!(statistics.total_size, 15 + 18 + 47);
+        let largest: Vec<&str> = statistics
+            .largest_symbols
+            .iter()
+            .map(|symbol| symbol.name.as_str())
+            .collect();
+        assert_eq!(largest, vec!["format", "Config"]);
+    }
+}
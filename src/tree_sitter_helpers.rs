@@ -1,11 +1,39 @@
+use crate::error::SourceDiagnostic;
+use crate::source_code::SourceCode;
 use crate::ExtractionError;
+use std::borrow::Cow;
 use std::ops::Range;
-use tree_sitter::{Node, Parser, Query, QueryCursor, QueryMatches, Tree};
+use std::sync::{Arc, OnceLock};
+use tree_sitter::{InputEdit, Node, Parser, Query, QueryCursor, QueryMatches, Tree};
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// Normalise source code so that it parses identically across platforms.
+///
+/// This strips the UTF-8 byte order mark, if any, and converts CRLF line endings to LF.
+///
+/// # Parameters
+/// * `source_code` - The source code as read from disk
+///
+/// # Returns
+/// The normalised source code, borrowed if no changes were needed
+pub fn normalise_source_code(source_code: &str) -> Cow<'_, str> {
+    let source_code = source_code
+        .strip_prefix(BYTE_ORDER_MARK)
+        .unwrap_or(source_code);
+    if source_code.contains("\r\n") {
+        Cow::Owned(source_code.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(source_code)
+    }
+}
 
 /// A parsed source file with its tree-sitter parse tree and original source code.
 pub struct ParsedFile<'a> {
     root_tree: Tree,
     source_code: &'a str,
+    /// The source code shared by the slices returned by [`ParsedFile::source_code`]
+    shared_source_code: OnceLock<Arc<str>>,
 }
 
 impl<'a> ParsedFile<'a> {
@@ -16,24 +44,123 @@ impl<'a> ParsedFile<'a> {
     /// * `parser` - A mutable reference to a configured tree-sitter parser
     ///
     /// # Returns
-    /// A new `ParsedFile` instance or an `ExtractionError` if parsing fails
+    /// A new `ParsedFile` instance or an `ExtractionError` if parsing fails,
+    /// which is a syntax error locating the first offending node if the source code is invalid
     pub fn parse(source_code: &'a str, parser: &mut Parser) -> Result<Self, ExtractionError> {
         let root_tree = parser
             .parse(source_code, None)
             .ok_or_else(|| ExtractionError::Malformed("Failed to parse source file".to_string()))?;
+        Self::from_tree(root_tree, source_code, OnceLock::new())
+    }
 
-        if root_tree.root_node().has_error() {
-            return Err(ExtractionError::Malformed(
-                "Failed to parse source file".to_string(),
-            ));
-        }
+    /// Parse shared source code, so that [`ParsedFile::source_code`] doesn't need to copy it.
+    ///
+    /// # Parameters
+    /// * `source_code` - The source code to parse
+    /// * `parser` - A mutable reference to a configured tree-sitter parser
+    ///
+    /// # Returns
+    /// A new `ParsedFile` instance or an `ExtractionError` if parsing fails, as with
+    /// [`ParsedFile::parse`]
+    pub fn parse_shared(
+        source_code: &'a Arc<str>,
+        parser: &mut Parser,
+    ) -> Result<Self, ExtractionError> {
+        let root_tree = parser
+            .parse(source_code.as_bytes(), None)
+            .ok_or_else(|| ExtractionError::Malformed("Failed to parse source file".to_string()))?;
+        Self::from_tree(
+            root_tree,
+            source_code,
+            OnceLock::from(Arc::clone(source_code)),
+        )
+    }
 
-        Ok(Self {
+    /// Re-parse the file incrementally after its source code has been edited.
+    ///
+    /// Unlike [`ParsedFile::parse`], this doesn't fail if the edited source code has syntax errors,
+    /// since a file being edited is often invalid temporarily and keeping its tree allows the next
+    /// edit to be re-parsed incrementally too. Use [`ParsedFile::syntax_error`] to check for them.
+    ///
+    /// # Parameters
+    /// * `source_code` - The source code after the edits
+    /// * `edits` - The edits applied to the previous source code, in the order they were made
+    /// * `parser` - A mutable reference to a configured tree-sitter parser
+    ///
+    /// # Returns
+    /// The re-parsed file along with the byte ranges that were edited or whose syntactic structure
+    /// changed, or an `ExtractionError` if the parser fails to produce a tree
+    pub fn edit<'b>(
+        mut self,
+        source_code: &'b str,
+        edits: &[InputEdit],
+        parser: &mut Parser,
+    ) -> Result<(ParsedFile<'b>, Vec<Range<usize>>), ExtractionError> {
+        for edit in edits {
+            self.root_tree.edit(edit);
+        }
+        let root_tree = parser
+            .parse(source_code, Some(&self.root_tree))
+            .ok_or_else(|| ExtractionError::Malformed("Failed to parse source file".to_string()))?;
+        // Edits that keep the syntactic structure (e.g. renaming an identifier) aren't reported
+        // by tree-sitter, so the edited ranges themselves are reported too.
+        let mut changed_ranges: Vec<Range<usize>> = vec![];
+        for edit in edits {
+            for range in &mut changed_ranges {
+                *range = shift_range(range, edit);
+            }
+            changed_ranges.push(edit.start_byte..edit.new_end_byte);
+        }
+        changed_ranges.extend(
+            self.root_tree
+                .changed_ranges(&root_tree)
+                .map(|range| range.start_byte..range.end_byte),
+        );
+        let changed_ranges = merge_ranges(changed_ranges);
+        let edited_file = ParsedFile {
             root_tree,
             source_code,
+            shared_source_code: OnceLock::new(),
+        };
+        Ok((edited_file, changed_ranges))
+    }
+
+    /// Locate the first syntax error in the file, if any.
+    ///
+    /// # Returns
+    /// A diagnostic pointing at the first offending node, or `None` if the file is valid
+    pub fn syntax_error(&self) -> Option<SourceDiagnostic> {
+        let error_node = find_error_node(self.root_tree.root_node())?;
+        let span = error_node.byte_range();
+        let snippet = self.source_code[span.clone()]
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        Some(SourceDiagnostic {
+            file: None,
+            span,
+            snippet,
+            message: "Failed to parse source file".to_string(),
         })
     }
 
+    fn from_tree(
+        root_tree: Tree,
+        source_code: &'a str,
+        shared_source_code: OnceLock<Arc<str>>,
+    ) -> Result<Self, ExtractionError> {
+        let parsed_file = Self {
+            root_tree,
+            source_code,
+            shared_source_code,
+        };
+        match parsed_file.syntax_error() {
+            Some(diagnostic) => Err(ExtractionError::Syntax(diagnostic)),
+            None => Ok(parsed_file),
+        }
+    }
+
     /// Return the root node of the parse tree.
     ///
     /// # Returns
@@ -55,6 +182,24 @@ impl<'a> ParsedFile<'a> {
             .map_err(|_| ExtractionError::Malformed("Failed to render node".to_string()))
     }
 
+    /// Return a tree-sitter node's source code as a slice sharing the file's contents.
+    ///
+    /// Unless the file was parsed with [`ParsedFile::parse_shared`], its contents are copied the
+    /// first time, and then shared by every slice taken from it.
+    ///
+    /// # Parameters
+    /// * `node` - The tree-sitter node to take the source code of
+    ///
+    /// # Returns
+    /// The source code of the node or an `ExtractionError` if the node isn't within the file
+    pub fn source_code(&self, node: Node) -> Result<SourceCode, ExtractionError> {
+        let shared_source_code = self
+            .shared_source_code
+            .get_or_init(|| Arc::from(self.source_code));
+        SourceCode::new(Arc::clone(shared_source_code), node.byte_range())
+            .ok_or_else(|| ExtractionError::Malformed("Failed to render node".to_string()))
+    }
+
     /// Return text content from a byte range in the source code.
     ///
     /// # Parameters
@@ -96,3 +241,233 @@ impl<'a> ParsedFile<'a> {
         cursor.matches(query, node, self.source_code.as_bytes())
     }
 }
+
+/// Map a byte range from before an edit to the source code after it.
+fn shift_range(range: &Range<usize>, edit: &InputEdit) -> Range<usize> {
+    let shift_start = |byte: usize| match byte {
+        _ if byte <= edit.start_byte => byte,
+        _ if byte < edit.old_end_byte => edit.start_byte,
+        _ => byte - edit.old_end_byte + edit.new_end_byte,
+    };
+    let shift_end = |byte: usize| match byte {
+        _ if byte <= edit.start_byte => byte,
+        _ if byte < edit.old_end_byte => edit.new_end_byte,
+        _ => byte - edit.old_end_byte + edit.new_end_byte,
+    };
+    shift_start(range.start)..shift_end(range.end)
+}
+
+/// Sort byte ranges and coalesce the ones that overlap or touch.
+fn merge_ranges(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_by_key(|range| (range.start, range.end));
+    let mut merged_ranges: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged_ranges.last_mut() {
+            Some(last_range) if range.start <= last_range.end => {
+                last_range.end = last_range.end.max(range.end);
+            }
+            _ => merged_ranges.push(range),
+        }
+    }
+    merged_ranges
+}
+
+fn find_error_node(node: Node) -> Option<Node> {
+    if node.is_error() || node.is_missing() {
+        return Some(node);
+    }
+    if !node.has_error() {
+        return None;
+    }
+    let mut cursor = node.walk();
+    let child_error_node = node.children(&mut cursor).find_map(find_error_node);
+    child_error_node.or(Some(node))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalise_source_code_unchanged() {
+        let source_code = "fn main() {}\n";
+
+        let normalised = normalise_source_code(source_code);
+
+        assert!(matches!(normalised, Cow::Borrowed(_)));
+        assert_eq!(normalised, source_code);
+    }
+
+    #[test]
+    fn normalise_source_code_crlf() {
+        let source_code = "/// Docs\r\nfn main() {}\r\n";
+
+        let normalised = normalise_source_code(source_code);
+
+        assert_eq!(normalised, "/// Docs\nfn main() {}\n");
+    }
+
+    #[test]
+    fn normalise_source_code_bom() {
+        let source_code = "\u{feff}//! Crate docs\n";
+
+        let normalised = normalise_source_code(source_code);
+
+        assert!(matches!(normalised, Cow::Borrowed(_)));
+        assert_eq!(normalised, "//! Crate docs\n");
+    }
+
+    #[test]
+    fn normalise_source_code_bom_and_crlf() {
+        let source_code = "\u{feff}//! Crate docs\r\n";
+
+        let normalised = normalise_source_code(source_code);
+
+        assert_eq!(normalised, "//! Crate docs\n");
+    }
+
+    fn make_rust_parser() -> Parser {
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_rust::LANGUAGE.into())
+            .unwrap();
+        parser
+    }
+
+    #[test]
+    fn parse_valid_source_code() {
+        let mut parser = make_rust_parser();
+
+        let parsed_file = ParsedFile::parse("fn run() {}", &mut parser).unwrap();
+
+        assert!(parsed_file.syntax_error().is_none());
+    }
+
+    #[test]
+    fn parse_reports_syntax_error() {
+        let mut parser = make_rust_parser();
+
+        let result = ParsedFile::parse("fn run() {}\nfn broken(x: {}\n", &mut parser);
+
+        let Err(ExtractionError::Syntax(diagnostic)) = result else {
+            panic!("Expected a syntax error");
+        };
+        assert_eq!(diagnostic.span, 12..24);
+        assert_eq!(diagnostic.snippet, "fn broken(x:");
+    }
+
+    #[test]
+    fn source_code_of_node() {
+        let mut parser = make_rust_parser();
+        let parsed_file = ParsedFile::parse("fn run() {}", &mut parser).unwrap();
+        let function_node = parsed_file.root_node().child(0).unwrap();
+        let name_node = function_node.child_by_field_name("name").unwrap();
+
+        let source_code = parsed_file.source_code(name_node).unwrap();
+
+        assert_eq!(source_code.as_str(), "run");
+        assert_eq!(source_code.range(), 3..6);
+    }
+
+    #[test]
+    fn source_code_of_shared_file() {
+        let mut parser = make_rust_parser();
+        let shared_source_code: Arc<str> = Arc::from("fn run() {}");
+        let parsed_file = ParsedFile::parse_shared(&shared_source_code, &mut parser).unwrap();
+
+        let source_code = parsed_file.source_code(parsed_file.root_node()).unwrap();
+
+        assert_eq!(source_code.as_str(), "fn run() {}");
+        assert_eq!(Arc::strong_count(&shared_source_code), 3);
+    }
+
+    /// Make the edit replacing `old_text` with `new_text` at `start_byte` on the first line.
+    fn make_edit(start_byte: usize, old_text: &str, new_text: &str) -> InputEdit {
+        let make_point = |column| tree_sitter::Point { row: 0, column };
+        InputEdit {
+            start_byte,
+            old_end_byte: start_byte + old_text.len(),
+            new_end_byte: start_byte + new_text.len(),
+            start_position: make_point(start_byte),
+            old_end_position: make_point(start_byte + old_text.len()),
+            new_end_position: make_point(start_byte + new_text.len()),
+        }
+    }
+
+    #[test]
+    fn edit_reports_changed_ranges() {
+        let mut parser = make_rust_parser();
+        let parsed_file = ParsedFile::parse("fn run() {}", &mut parser).unwrap();
+
+        let (edited_file, changed_ranges) = parsed_file
+            .edit(
+                "fn run(x: u8) {}",
+                &[make_edit(7, "", "x: u8")],
+                &mut parser,
+            )
+            .unwrap();
+
+        assert!(edited_file.syntax_error().is_none());
+        assert_eq!(changed_ranges, vec![7..12]);
+        assert_eq!(
+            edited_file.render_node(edited_file.root_node()).unwrap(),
+            "fn run(x: u8) {}"
+        );
+    }
+
+    #[test]
+    fn edit_reports_renamed_identifier() {
+        let mut parser = make_rust_parser();
+        let parsed_file = ParsedFile::parse("fn run() {}", &mut parser).unwrap();
+
+        let (_, changed_ranges) = parsed_file
+            .edit("fn ran() {}", &[make_edit(3, "run", "ran")], &mut parser)
+            .unwrap();
+
+        assert_eq!(changed_ranges, vec![3..6]);
+    }
+
+    #[test]
+    fn edit_reports_doc_comment_text() {
+        let mut parser = make_rust_parser();
+        let parsed_file = ParsedFile::parse("/// a\nfn run() {}", &mut parser).unwrap();
+
+        let (_, changed_ranges) = parsed_file
+            .edit("/// b\nfn run() {}", &[make_edit(4, "a", "b")], &mut parser)
+            .unwrap();
+
+        assert_eq!(changed_ranges, vec![4..5]);
+    }
+
+    #[test]
+    fn edit_shifts_earlier_edits() {
+        let mut parser = make_rust_parser();
+        let parsed_file = ParsedFile::parse("fn a() {} fn b() {}", &mut parser).unwrap();
+
+        let (_, changed_ranges) = parsed_file
+            .edit(
+                "fn bb() {} fn c() {}",
+                &[make_edit(13, "b", "c"), make_edit(0, "fn a", "fn bb")],
+                &mut parser,
+            )
+            .unwrap();
+
+        assert_eq!(changed_ranges, vec![0..5, 14..15]);
+    }
+
+    #[test]
+    fn edit_keeps_file_with_syntax_error() {
+        let mut parser = make_rust_parser();
+        let parsed_file = ParsedFile::parse("fn run() {}", &mut parser).unwrap();
+
+        let (invalid_file, _) = parsed_file
+            .edit("fn run( {}", &[make_edit(7, ")", "")], &mut parser)
+            .unwrap();
+        assert!(invalid_file.syntax_error().is_some());
+        let (fixed_file, _) = invalid_file
+            .edit("fn run() {}", &[make_edit(7, "", ")")], &mut parser)
+            .unwrap();
+
+        assert!(fixed_file.syntax_error().is_none());
+    }
+}
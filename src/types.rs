@@ -1,39 +1,302 @@
+use crate::source_code::SourceCode;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// A module or package of a library's public API, along with the symbols it exports.
+///
+/// Use [`Namespace::new`] and then set any other fields that are known.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub struct Namespace {
     pub name: String,
     pub symbols: Vec<Symbol>,
     pub doc_comment: Option<String>,
+    /// Names of symbols or sub-namespaces that could not be resolved
+    pub missing_symbols: Vec<String>,
 }
 
+/// A symbol exported by a namespace, such as a function or type.
+///
+/// Use [`Symbol::new`] and then set any other fields that are known.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub struct Symbol {
     pub name: String,
-    pub source_code: String,
+    pub source_code: SourceCode,
+    /// The file where the symbol is defined
+    pub file: Arc<Path>,
+    /// The line where the symbol definition starts (1-based)
+    pub start_line: usize,
+    /// The line where the symbol definition ends (1-based, inclusive)
+    pub end_line: usize,
+    pub kind: SymbolKind,
+    /// The features (e.g. Cargo features, Python extras) that must be enabled for the symbol to
+    /// be available, such as those declared with Rust's `doc(cfg(feature = "..."))`
+    pub required_features: Vec<String>,
+    /// Whether the symbol is a trait or type that code outside the library can't implement or
+    /// extend, such as a Rust sealed trait or a Kotlin sealed interface
+    pub is_sealed: bool,
+    /// The condition under which this definition applies (e.g. `unix`), if it's conditional
+    pub condition: Option<String>,
+    /// The definitions of the symbol under other conditions (e.g. for other platforms)
+    pub variants: Vec<SymbolVariant>,
+}
+
+/// An alternative definition of a symbol that applies under a different condition
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SymbolVariant {
+    /// The condition under which this definition applies (e.g. `windows`)
+    pub condition: String,
+    pub source_code: SourceCode,
+    /// The file where the definition is
+    pub file: Arc<Path>,
+    /// The line where the definition starts (1-based)
+    pub start_line: usize,
+    /// The line where the definition ends (1-based, inclusive)
+    pub end_line: usize,
+}
+
+/// The kind of a symbol, as far as it can be described across languages.
+///
+/// Variants are declared in the order symbols are grouped in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum SymbolKind {
+    /// A data type, such as a struct, enum, union, class or type alias
+    Type,
+    /// An abstract interface, such as a trait, interface or protocol
+    Trait,
+    /// A function or method
+    Function,
+    /// A constant or static variable
+    Constant,
+    /// A macro
+    Macro,
+    /// Anything else
+    Other,
+}
+
+/// The order to sort symbols in within a namespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum SymbolOrder {
+    /// By file and line of definition
+    #[default]
+    Source,
+    /// Alphabetically by name
+    Name,
+}
+
+/// A non-fatal problem encountered whilst extracting the public API
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtractionWarning {
+    /// The file the problem relates to, if any
+    pub file: Option<PathBuf>,
+    pub message: String,
+}
+
+/// The public API of a library, along with any problems that didn't prevent its extraction
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtractionReport {
+    pub namespaces: Vec<Namespace>,
+    pub warnings: Vec<ExtractionWarning>,
 }
 
 impl Namespace {
+    /// Create a namespace, without documentation or missing symbols.
+    ///
+    /// # Parameters
+    /// * `name` - The name of the namespace (e.g. `crate::module`)
+    /// * `symbols` - The symbols in the namespace
+    ///
+    /// # Returns
+    /// The namespace
+    pub fn new(name: impl Into<String>, symbols: Vec<Symbol>) -> Self {
+        Self {
+            name: name.into(),
+            symbols,
+            doc_comment: None,
+            missing_symbols: Vec::new(),
+        }
+    }
+
     pub fn get_symbol(&self, name: &str) -> Option<&Symbol> {
         self.symbols.iter().find(|s| s.name == name)
     }
+
+    /// Sort the symbols by their position in the source code, and the missing symbols by name.
+    pub fn sort(&mut self) {
+        self.sort_symbols(SymbolOrder::Source, false);
+        self.missing_symbols.sort();
+    }
+
+    /// Sort the symbols, optionally grouping them by kind first.
+    ///
+    /// # Parameters
+    /// * `order` - The order of the symbols (within each group, if grouping)
+    /// * `group_by_kind` - Whether to group the symbols by kind, in the order of `SymbolKind`
+    pub fn sort_symbols(&mut self, order: SymbolOrder, group_by_kind: bool) {
+        self.symbols.sort_by(|a, b| {
+            let kind_ordering = if group_by_kind {
+                a.kind.cmp(&b.kind)
+            } else {
+                std::cmp::Ordering::Equal
+            };
+            let symbol_ordering = match order {
+                SymbolOrder::Source => {
+                    (&a.file, a.start_line, &a.name).cmp(&(&b.file, b.start_line, &b.name))
+                }
+                SymbolOrder::Name => {
+                    (&a.name, &a.file, a.start_line).cmp(&(&b.name, &b.file, b.start_line))
+                }
+            };
+            kind_ordering.then(symbol_ordering)
+        });
+    }
+
+    /// Merge the conditional definitions of each symbol into a single entry.
+    ///
+    /// Symbols sharing a name and kind that are all conditional (e.g. one definition for Unix
+    /// and another for Windows) become variants of the first one, instead of duplicates.
+    pub fn merge_conditional_symbols(&mut self) {
+        let mut merged_symbols: Vec<Symbol> = Vec::with_capacity(self.symbols.len());
+        for symbol in self.symbols.drain(..) {
+            let Some(condition) = symbol.condition.clone() else {
+                merged_symbols.push(symbol);
+                continue;
+            };
+            let primary_symbol = merged_symbols.iter_mut().find(|merged_symbol| {
+                merged_symbol.condition.is_some()
+                    && merged_symbol.name == symbol.name
+                    && merged_symbol.kind == symbol.kind
+            });
+            match primary_symbol {
+                Some(primary_symbol) => {
+                    primary_symbol.variants.push(SymbolVariant {
+                        condition,
+                        source_code: symbol.source_code,
+                        file: symbol.file,
+                        start_line: symbol.start_line,
+                        end_line: symbol.end_line,
+                    });
+                    primary_symbol.variants.extend(symbol.variants);
+                }
+                None => merged_symbols.push(symbol),
+            }
+        }
+        self.symbols = merged_symbols;
+    }
+}
+
+impl Symbol {
+    /// Create an unconditional symbol that requires no features and isn't sealed.
+    ///
+    /// # Parameters
+    /// * `name` - The name of the symbol
+    /// * `source_code` - The source code of the symbol's definition
+    /// * `file` - The file where the symbol is defined
+    /// * `start_line` - The line where the symbol definition starts (1-based)
+    /// * `end_line` - The line where the symbol definition ends (1-based, inclusive)
+    /// * `kind` - The kind of the symbol
+    ///
+    /// # Returns
+    /// The symbol, without a condition or variants
+    pub fn new(
+        name: impl Into<String>,
+        source_code: impl Into<SourceCode>,
+        file: impl Into<Arc<Path>>,
+        start_line: usize,
+        end_line: usize,
+        kind: SymbolKind,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            source_code: source_code.into(),
+            file: file.into(),
+            start_line,
+            end_line,
+            kind,
+            required_features: Vec::new(),
+            is_sealed: false,
+            condition: None,
+            variants: Vec::new(),
+        }
+    }
+
+    /// Return the location of the symbol's definition, in the form `path/to/file.rs:42`.
+    pub fn location(&self) -> String {
+        format!("{}:{}", self.file.display(), self.start_line)
+    }
+
+    /// Check whether the symbol is available with the given features enabled.
+    ///
+    /// # Parameters
+    /// * `enabled_features` - The features considered enabled (e.g. `ExtractorOptions::features`)
+    ///
+    /// # Returns
+    /// Whether all the features required by the symbol are enabled
+    pub fn is_available_with(&self, enabled_features: &[String]) -> bool {
+        self.required_features
+            .iter()
+            .all(|feature| enabled_features.contains(feature))
+    }
+}
+
+impl ExtractionReport {
+    /// Sort the namespaces by name and each namespace's symbols by source position,
+    /// so that the output is identical across runs.
+    pub fn sort(&mut self) {
+        self.namespaces.sort_by(|a, b| a.name.cmp(&b.name));
+        for namespace in &mut self.namespaces {
+            namespace.sort();
+        }
+    }
+}
+
+/// Factories for the symbols and namespaces used in tests across the crate.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::*;
+
+    /// Make a function symbol defined at the start of `src/lib.rs`.
+    ///
+    /// Tests needing other values should override the relevant fields with struct update syntax.
+    pub(crate) fn make_symbol(name: &str, source_code: &str) -> Symbol {
+        Symbol::new(
+            name,
+            source_code,
+            Path::new("src/lib.rs"),
+            1,
+            source_code.lines().count().max(1),
+            SymbolKind::Function,
+        )
+    }
+
+    pub(crate) fn make_namespace(name: &str, symbols: Vec<Symbol>) -> Namespace {
+        Namespace::new(name, symbols)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use assertables::{assert_none, assert_some};
 
+    use super::fixtures::make_namespace;
     use super::*;
 
     #[test]
     fn get_symbol_found() {
-        let symbol = Symbol {
-            name: "test_symbol".to_string(),
-            source_code: "fn test() {}".to_string(),
-        };
-        let namespace = Namespace {
-            name: "test_namespace".to_string(),
-            symbols: vec![symbol],
-            doc_comment: None,
-        };
+        let namespace = make_namespace(
+            "test_namespace",
+            vec![fixtures::make_symbol("test_symbol", "fn test() {}")],
+        );
 
         let found = namespace.get_symbol("test_symbol");
 
@@ -43,14 +306,207 @@ mod tests {
 
     #[test]
     fn get_symbol_not_found() {
-        let namespace = Namespace {
-            name: "test_namespace".to_string(),
-            symbols: vec![],
-            doc_comment: None,
-        };
+        let namespace = make_namespace("test_namespace", vec![]);
 
         let symbol = namespace.get_symbol("nonexistent");
 
         assert_none!(symbol);
     }
+
+    fn make_symbol(name: &str, file: &str, start_line: usize) -> Symbol {
+        make_symbol_of_kind(name, file, start_line, SymbolKind::Function)
+    }
+
+    fn make_symbol_of_kind(name: &str, file: &str, start_line: usize, kind: SymbolKind) -> Symbol {
+        Symbol {
+            file: Arc::from(Path::new(file)),
+            start_line,
+            end_line: start_line,
+            kind,
+            ..fixtures::make_symbol(name, &format!("fn {}() {{}}", name))
+        }
+    }
+
+    #[test]
+    fn symbol_location() {
+        let symbol = make_symbol("format", "src/text/formatter.rs", 42);
+
+        assert_eq!(symbol.location(), "src/text/formatter.rs:42");
+    }
+
+    #[test]
+    fn symbol_availability() {
+        let symbol = Symbol {
+            required_features: vec!["rt".to_string(), "net".to_string()],
+            ..make_symbol("spawn", "src/lib.rs", 1)
+        };
+        let ungated_symbol = make_symbol("run", "src/lib.rs", 2);
+
+        assert!(symbol.is_available_with(&["net".to_string(), "rt".to_string()]));
+        assert!(!symbol.is_available_with(&["rt".to_string()]));
+        assert!(ungated_symbol.is_available_with(&[]));
+    }
+
+    #[test]
+    fn sort_report() {
+        let mut report = ExtractionReport {
+            namespaces: vec![
+                Namespace {
+                    missing_symbols: vec!["b".to_string(), "a".to_string()],
+                    ..make_namespace(
+                        "crate::text",
+                        vec![
+                            make_symbol("format", "src/text.rs", 20),
+                            make_symbol("parse", "src/text.rs", 3),
+                        ],
+                    )
+                },
+                make_namespace(
+                    "crate",
+                    vec![
+                        make_symbol("from_text", "src/text.rs", 1),
+                        make_symbol("run", "src/lib.rs", 10),
+                    ],
+                ),
+            ],
+            warnings: vec![],
+        };
+
+        report.sort();
+
+        let names: Vec<Vec<&str>> = report
+            .namespaces
+            .iter()
+            .map(|namespace| namespace.symbols.iter().map(|s| s.name.as_str()).collect())
+            .collect();
+        assert_eq!(report.namespaces[0].name, "crate");
+        assert_eq!(
+            names,
+            vec![vec!["run", "from_text"], vec!["parse", "format"]]
+        );
+        assert_eq!(report.namespaces[1].missing_symbols, vec!["a", "b"]);
+    }
+
+    fn get_symbol_names(namespace: &Namespace) -> Vec<&str> {
+        namespace.symbols.iter().map(|s| s.name.as_str()).collect()
+    }
+
+    #[test]
+    fn sort_symbols_by_name() {
+        let mut namespace = make_namespace(
+            "crate",
+            vec![
+                make_symbol("parse", "src/lib.rs", 1),
+                make_symbol("format", "src/lib.rs", 2),
+            ],
+        );
+
+        namespace.sort_symbols(SymbolOrder::Name, false);
+
+        assert_eq!(get_symbol_names(&namespace), vec!["format", "parse"]);
+    }
+
+    #[test]
+    fn sort_symbols_grouped_by_kind() {
+        let mut namespace = make_namespace(
+            "crate",
+            vec![
+                make_symbol_of_kind("run", "src/lib.rs", 1, SymbolKind::Function),
+                make_symbol_of_kind("Runnable", "src/lib.rs", 2, SymbolKind::Trait),
+                make_symbol_of_kind("Runner", "src/lib.rs", 3, SymbolKind::Type),
+                make_symbol_of_kind("Config", "src/lib.rs", 4, SymbolKind::Type),
+            ],
+        );
+
+        namespace.sort_symbols(SymbolOrder::Source, true);
+
+        assert_eq!(
+            get_symbol_names(&namespace),
+            vec!["Runner", "Config", "Runnable", "run"]
+        );
+    }
+
+    fn make_conditional_symbol(name: &str, start_line: usize, condition: &str) -> Symbol {
+        Symbol {
+            condition: Some(condition.to_string()),
+            ..make_symbol(name, "src/lib.rs", start_line)
+        }
+    }
+
+    #[test]
+    fn merge_conditional_symbols() {
+        let mut namespace = make_namespace(
+            "crate",
+            vec![
+                make_conditional_symbol("connect", 1, "unix"),
+                make_symbol("connect", "src/lib.rs", 2),
+                make_conditional_symbol("connect", 3, "windows"),
+                make_symbol_of_kind("connect", "src/lib.rs", 4, SymbolKind::Macro),
+            ],
+        );
+
+        namespace.merge_conditional_symbols();
+
+        assert_eq!(namespace.symbols.len(), 3);
+        let merged_symbol = &namespace.symbols[0];
+        assert_eq!(merged_symbol.condition.as_deref(), Some("unix"));
+        assert_eq!(
+            merged_symbol.variants,
+            vec![SymbolVariant {
+                condition: "windows".to_string(),
+                source_code: "fn connect() {}".into(),
+                file: Arc::from(Path::new("src/lib.rs")),
+                start_line: 3,
+                end_line: 3,
+            }]
+        );
+        assert!(namespace.symbols[1].variants.is_empty());
+        assert!(namespace.symbols[2].variants.is_empty());
+    }
+
+    #[test]
+    fn merge_conditional_symbols_with_variants() {
+        let make_variant = |condition: &str, start_line| SymbolVariant {
+            condition: condition.to_string(),
+            source_code: "fn connect() {}".into(),
+            file: Arc::from(Path::new("src/lib.rs")),
+            start_line,
+            end_line: start_line,
+        };
+        let mut namespace = make_namespace(
+            "crate",
+            vec![
+                make_conditional_symbol("connect", 1, "unix"),
+                Symbol {
+                    variants: vec![make_variant("wasm", 3)],
+                    ..make_conditional_symbol("connect", 2, "windows")
+                },
+            ],
+        );
+
+        namespace.merge_conditional_symbols();
+
+        assert_eq!(namespace.symbols.len(), 1);
+        assert_eq!(
+            namespace.symbols[0].variants,
+            vec![make_variant("windows", 2), make_variant("wasm", 3)]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn namespace_serde_round_trip() {
+        let namespace = Namespace {
+            doc_comment: Some("Docs".to_string()),
+            ..make_namespace(
+                "test_namespace",
+                vec![fixtures::make_symbol("test_symbol", "fn test() {}")],
+            )
+        };
+
+        let json = serde_json::to_string(&namespace).unwrap();
+        let deserialised: Namespace = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialised, namespace);
+    }
 }
@@ -0,0 +1,83 @@
+use crate::types::{Namespace, Symbol};
+
+/// Visit the namespaces and symbols of an extracted public API.
+///
+/// All methods do nothing by default, so implementors only need to override the ones they use.
+pub trait SymbolVisitor {
+    /// Visit a namespace, before any of its symbols are visited
+    fn visit_namespace(&mut self, _namespace: &Namespace) {}
+
+    /// Visit a symbol along with the namespace it belongs to
+    fn visit_symbol(&mut self, _namespace: &Namespace, _symbol: &Symbol) {}
+}
+
+/// Walk the namespaces and their symbols in order.
+///
+/// # Parameters
+/// * `namespaces` - The namespaces to walk
+/// * `visitor` - The visitor to call for each namespace and symbol
+pub fn walk(namespaces: &[Namespace], visitor: &mut dyn SymbolVisitor) {
+    for namespace in namespaces {
+        namespace.walk(visitor);
+    }
+}
+
+impl Namespace {
+    /// Walk the namespace and its symbols in order.
+    pub fn walk(&self, visitor: &mut dyn SymbolVisitor) {
+        visitor.visit_namespace(self);
+        for symbol in &self.symbols {
+            visitor.visit_symbol(self, symbol);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::fixtures::{make_namespace, make_symbol};
+
+    #[derive(Default)]
+    struct RecordingVisitor {
+        visits: Vec<String>,
+    }
+
+    impl SymbolVisitor for RecordingVisitor {
+        fn visit_namespace(&mut self, namespace: &Namespace) {
+            self.visits.push(namespace.name.clone());
+        }
+
+        fn visit_symbol(&mut self, namespace: &Namespace, symbol: &Symbol) {
+            self.visits
+                .push(format!("{}::{}", namespace.name, symbol.name));
+        }
+    }
+
+    #[test]
+    fn walk_visits_namespaces_then_symbols() {
+        let namespaces = vec![
+            make_namespace(
+                "crate",
+                vec![
+                    make_symbol("foo", "fn foo() {}"),
+                    make_symbol("bar", "fn bar() {}"),
+                ],
+            ),
+            make_namespace("crate::inner", vec![make_symbol("baz", "fn baz() {}")]),
+        ];
+        let mut visitor = RecordingVisitor::default();
+
+        walk(&namespaces, &mut visitor);
+
+        assert_eq!(
+            visitor.visits,
+            vec![
+                "crate",
+                "crate::foo",
+                "crate::bar",
+                "crate::inner",
+                "crate::inner::baz"
+            ]
+        );
+    }
+}